@@ -1,8 +1,9 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use destination::{
-    GrantsPassSpatialAddresses, IntoCsv, JosephineCountySpatialAddresses2024, MatchRecords,
-    SpatialAddresses,
+    GrantsPassSpatialAddresses, IntoCsv, JosephineCountySpatialAddresses2024, MatchPolicy,
+    MatchRecords, SpatialAddresses,
 };
+use rayon::prelude::*;
 
 pub fn compare_records(c: &mut Criterion) {
     let city_path = "data/city_addresses_20241007.csv";
@@ -18,7 +19,35 @@ pub fn compare_records(c: &mut Criterion) {
     ));
     group.bench_function("compare records", |b| {
         b.iter(|| {
-            MatchRecords::compare(&source_addresses[0..10], &target_addresses[0..1000]);
+            MatchRecords::compare(
+                &source_addresses[0..10],
+                &target_addresses[0..1000],
+                &MatchPolicy::default(),
+            );
+        })
+    });
+    group.finish();
+
+    // Compares a slice of the city file against the full county file.  The linear scan calls
+    // `MatchRecords::new` against every target address, so both approaches run over the same
+    // slice of source addresses, keeping the comparison like-for-like.
+    let mut group = c.benchmark_group("full datasets");
+    group.sample_size(10);
+    let sources = &source_addresses[0..100];
+    group.throughput(criterion::Throughput::Elements(sources.len() as u64));
+    group.bench_function("linear scan", |b| {
+        b.iter(|| {
+            sources
+                .par_iter()
+                .map(|address| {
+                    MatchRecords::new(address, &target_addresses, &MatchPolicy::default())
+                })
+                .collect::<Vec<MatchRecords>>();
+        })
+    });
+    group.bench_function("indexed compare", |b| {
+        b.iter(|| {
+            MatchRecords::compare(sources, &target_addresses, &MatchPolicy::default());
         })
     });
     group.finish();
@@ -1,15 +1,15 @@
 //! The `address` module defines the library data standard for a valid address, and provides
 //! implementation blocks to convert data from import types to the valid address format.
 use crate::{
-    from_bin, from_csv, to_bin, to_csv, AddressError, AddressErrorKind, AddressMatch,
-    AddressStatus, Bincode, Builder, Cartesian, FireInspections, Geographic, IntoBin, IntoCsv, Io,
-    LexisNexis, Mismatch, Parse, PostalCommunity, State, StreetNamePostType,
-    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
-    SubaddressType,
+    from_bin, from_csv, to_bin, to_csv, to_geojson, AddressError, AddressErrorKind, AddressMatch,
+    AddressNumberRange, AddressStatus, Bincode, Builder, Cartesian, ComponentPolicy,
+    FireInspections, Geographic, IntoBin, IntoCsv, IntoGeoJson, Io, LexisNexis, MatchPolicy,
+    Mismatch, OrdinalStyle, Parse, PostalCommunity, PostalDelivery, PostalDeliveryType, Projection,
+    Rules, State, StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier,
+    StreetNamePreType, StreetSeparator, SubaddressType,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ProgressBar;
-use nom::bytes::complete::tag;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::ops;
@@ -51,6 +51,13 @@ pub trait Address {
     fn street_type(&self) -> &Option<StreetNamePostType>;
     /// The `street_type_mut` method returns a mutable reference to the street name post type component.
     fn street_type_mut(&mut self) -> &mut Option<StreetNamePostType>;
+    /// The `street_name_post_directional` method returns the street name post directional
+    /// component, if any.  Post directionals take the same values as predirectionals, so the
+    /// component uses the [`StreetNamePreDirectional`] type.
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional>;
+    /// The `street_name_post_directional_mut` method returns a mutable reference to the street
+    /// name post directional component.
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional>;
     /// The `subaddress_id` method returns the subaddress identifier component, if any.
     fn subaddress_id(&self) -> &Option<String>;
     /// The `subaddress_id_mut` method returns a mutable reference to the vale of the subaddress identifier component.
@@ -94,43 +101,166 @@ pub trait Address {
     /// An address is coincident when the `other` address refers to the same assignment or
     /// location.  If the addresses are coincident, but details (such as the floor number or
     /// address status) differ, then the differences are recorded as a vector of type [`Mismatch`].
-    /// The results are converted to type [`AddressMatch`].
+    /// The results are converted to type [`AddressMatch`].  Streets on numbered highway routes
+    /// compare using [`StreetNamePreType::same_street`].
     #[tracing::instrument(skip_all)]
     fn coincident<T: Address>(&self, other: &T) -> AddressMatch {
-        let mut coincident = false;
+        self.coincident_with(other, &MatchPolicy::default())
+    }
+
+    /// The `coincident_with` method compares the address to `other` like
+    /// [`Address::coincident`], but applies `policy` to decide which components must agree, which
+    /// differences are reported as a [`Mismatch`], and which are ignored.
+    #[tracing::instrument(skip_all)]
+    fn coincident_with<T: Address>(&self, other: &T, policy: &MatchPolicy) -> AddressMatch {
+        let mut coincident = true;
         let mut mismatches = Vec::new();
-        if self.number() == other.number()
-            && self.number_suffix() == other.number_suffix()
-            && self.directional() == other.directional()
-            && self.street_name_pre_modifier() == other.street_name_pre_modifier()
-            && self.street_name_pre_type() == other.street_name_pre_type()
-            && self.street_name_separator() == other.street_name_separator()
-            && self.street_name() == other.street_name()
-            && self.street_type() == other.street_type()
-            && self.subaddress_id() == other.subaddress_id()
-            && self.zip() == other.zip()
-            && self.postal_community() == other.postal_community()
-            && self.state() == other.state()
-        {
-            coincident = true;
-            if self.subaddress_type() != other.subaddress_type() {
-                mismatches.push(Mismatch::subaddress_type(
-                    *self.subaddress_type(),
-                    *other.subaddress_type(),
-                ));
+        // Equality checks are lazy, so that comparisons stop at the first required component
+        // that differs.
+        let mut check = |rule: ComponentPolicy,
+                         equal: &dyn Fn() -> bool,
+                         mismatch: &dyn Fn() -> Option<Mismatch>| {
+            if !coincident || rule == ComponentPolicy::Ignored || equal() {
+                return;
             }
-            if self.floor() != other.floor() {
-                mismatches.push(Mismatch::floor(*self.floor(), *other.floor()));
+            match rule {
+                ComponentPolicy::Required => coincident = false,
+                ComponentPolicy::Reported => mismatches.extend(mismatch()),
+                ComponentPolicy::Ignored => {}
             }
-            if self.building() != other.building() {
-                mismatches.push(Mismatch::building(
+        };
+        check(policy.number, &|| self.number() == other.number(), &|| {
+            Some(Mismatch::number(self.number(), other.number()))
+        });
+        check(
+            policy.number_suffix,
+            &|| self.number_suffix() == other.number_suffix(),
+            &|| {
+                Some(Mismatch::number_suffix(
+                    self.number_suffix().clone(),
+                    other.number_suffix().clone(),
+                ))
+            },
+        );
+        check(
+            policy.directional,
+            &|| self.directional() == other.directional(),
+            &|| {
+                Some(Mismatch::directional(
+                    *self.directional(),
+                    *other.directional(),
+                ))
+            },
+        );
+        check(
+            policy.pre_modifier,
+            &|| self.street_name_pre_modifier() == other.street_name_pre_modifier(),
+            &|| {
+                Some(Mismatch::pre_modifier(
+                    *self.street_name_pre_modifier(),
+                    *other.street_name_pre_modifier(),
+                ))
+            },
+        );
+        check(
+            policy.street_name,
+            &|| {
+                StreetNamePreType::same_street(
+                    (*self.street_name_pre_type(), self.street_name()),
+                    (*other.street_name_pre_type(), other.street_name()),
+                )
+            },
+            &|| {
+                Some(Mismatch::street_name(
+                    self.common_street_name(),
+                    other.common_street_name(),
+                ))
+            },
+        );
+        check(
+            policy.separator,
+            &|| self.street_name_separator() == other.street_name_separator(),
+            &|| {
+                Some(Mismatch::separator(
+                    *self.street_name_separator(),
+                    *other.street_name_separator(),
+                ))
+            },
+        );
+        check(
+            policy.street_type,
+            &|| self.street_type() == other.street_type(),
+            &|| {
+                Some(Mismatch::street_type(
+                    *self.street_type(),
+                    *other.street_type(),
+                ))
+            },
+        );
+        check(
+            policy.post_directional,
+            &|| self.street_name_post_directional() == other.street_name_post_directional(),
+            &|| {
+                Some(Mismatch::post_directional(
+                    *self.street_name_post_directional(),
+                    *other.street_name_post_directional(),
+                ))
+            },
+        );
+        check(
+            policy.subaddress_id,
+            &|| self.subaddress_id() == other.subaddress_id(),
+            &|| {
+                Some(Mismatch::subaddress_id(
+                    self.subaddress_id().clone(),
+                    other.subaddress_id().clone(),
+                ))
+            },
+        );
+        check(policy.zip, &|| self.zip() == other.zip(), &|| {
+            Some(Mismatch::zip(self.zip(), other.zip()))
+        });
+        check(
+            policy.postal_community,
+            &|| self.postal_community() == other.postal_community(),
+            &|| {
+                Some(Mismatch::postal_community(
+                    self.postal_community().clone(),
+                    other.postal_community().clone(),
+                ))
+            },
+        );
+        check(policy.state, &|| self.state() == other.state(), &|| {
+            Some(Mismatch::state(*self.state(), *other.state()))
+        });
+        check(
+            policy.subaddress_type,
+            &|| self.subaddress_type() == other.subaddress_type(),
+            &|| {
+                Some(Mismatch::subaddress_type(
+                    *self.subaddress_type(),
+                    *other.subaddress_type(),
+                ))
+            },
+        );
+        check(policy.floor, &|| self.floor() == other.floor(), &|| {
+            Some(Mismatch::floor(*self.floor(), *other.floor()))
+        });
+        check(
+            policy.building,
+            &|| self.building() == other.building(),
+            &|| {
+                Some(Mismatch::building(
                     self.building().clone(),
                     other.building().clone(),
-                ));
-            }
-            if self.status() != other.status() {
-                mismatches.push(Mismatch::status(*self.status(), *other.status()));
-            }
+                ))
+            },
+        );
+        check(policy.status, &|| self.status() == other.status(), &|| {
+            Some(Mismatch::status(*self.status(), *other.status()))
+        });
+        if !coincident {
+            mismatches.clear();
         }
         AddressMatch::new(coincident, mismatches)
     }
@@ -139,83 +269,27 @@ pub trait Address {
     /// complete street name and complete subaddress, used to produce map or mailing labels.
     #[tracing::instrument(skip_all)]
     fn label(&self) -> String {
-        let complete_address_number = match &self.number_suffix() {
-            Some(suffix) => format!("{} {}", self.number(), suffix),
-            None => self.number().to_string(),
-        };
-
-        let complete_street_name = self.complete_street_name(true);
-        tracing::trace!("Street name: {complete_street_name}");
-
-        let accessory = self.building().as_ref().map(|v| format!("BLDG {v}"));
-
-        let complete_subaddress = match &self.subaddress_id() {
-            Some(identifier) => match self.subaddress_type() {
-                Some(subaddress_type) => {
-                    Some(format!("{} {}", subaddress_type.abbreviate(), identifier))
-                }
-                None => Some(format!("#{}", identifier)),
-            },
-            None => self
-                .subaddress_type()
-                .map(|subaddress_type| subaddress_type.abbreviate()),
-        };
+        address_label(self, &self.complete_street_name(true))
+    }
 
-        match complete_subaddress {
-            Some(subaddress) => format!(
-                "{} {} {}",
-                complete_address_number, complete_street_name, subaddress
-            ),
-            None => match accessory {
-                Some(value) => format!(
-                    "{} {} {}",
-                    complete_address_number, complete_street_name, value
-                ),
-                None => format!("{} {}", complete_address_number, complete_street_name),
-            },
-        }
+    /// The `label_with` method returns the address label with the ordinals in the street name
+    /// printed in the style `style`, e.g. "100 SIXTH ST" in the [`OrdinalStyle::Spelled`] style.
+    #[tracing::instrument(skip_all)]
+    fn label_with(&self, style: OrdinalStyle) -> String {
+        address_label(self, &self.complete_street_name_with(true, style))
     }
 
     /// The `complete_street_name` method returns the complete street name of the address.
     #[tracing::instrument(skip_all)]
     fn complete_street_name(&self, abbreviate: bool) -> String {
-        let mut name = String::new();
-        if let Some(directional) = self.directional() {
-            if abbreviate {
-                if let Some(dir) = &self.directional_abbreviated() {
-                    name.push_str(dir);
-                }
-            } else {
-                name.push_str(&directional.to_string());
-            }
-            name.push(' ');
-        }
-        if let Some(modifier) = self.street_name_pre_modifier() {
-            name.push_str(modifier.upper().as_str());
-            name.push(' ');
-        }
-        if let Some(pre_type) = self.street_name_pre_type() {
-            name.push_str(pre_type.upper().as_str());
-            name.push(' ');
-        }
-        if let Some(separator) = self.street_name_separator() {
-            name.push_str(separator.upper().as_str());
-            name.push(' ');
-        }
-        name.push_str(&self.street_name().to_string());
-        if let Some(post_type) = self.street_type() {
-            tracing::trace!("Post type found: {post_type}");
-            name.push(' ');
-            if abbreviate {
-                tracing::trace!("Abbreviated: {}", post_type.abbreviate());
-                name.push_str(&post_type.abbreviate());
-            } else {
-                name.push_str(&post_type.to_string());
-            }
-        } else {
-            tracing::warn!("Post type not found for {name}.");
-        }
-        name
+        complete_street_name(self, abbreviate, self.street_name())
+    }
+
+    /// The `complete_street_name_with` method returns the complete street name of the address,
+    /// with the ordinals in the street name printed in the style `style`.
+    #[tracing::instrument(skip_all)]
+    fn complete_street_name_with(&self, abbreviate: bool, style: OrdinalStyle) -> String {
+        complete_street_name(self, abbreviate, &style.format(self.street_name()))
     }
 
     /// The `common_street_name` method returns the street name, including any premodifier, pretype
@@ -270,123 +344,111 @@ pub trait Address {
     }
 
     /// The `standardize` method takes county address naming conventions and converts them to city
-    /// naming conventions.
+    /// naming conventions, applying the default rule set from [`Rules::default_rules`].
     #[tracing::instrument(skip_all)]
     fn standardize(&mut self) {
-        let comp = self.street_name().clone();
-        if comp == "AZALEA DRIVE" {
-            trace!("Fixing Azalea Drive Cutoff");
-            *self.street_name_mut() = "AZALEA".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::DriveCutoff);
-        }
+        Rules::default_rules().apply(self);
+    }
 
-        if let Some(sub) = self.subaddress_id() {
-            if comp == "LEWIS" && sub == "OFFICE" {
-                info!("Fixing Lewis Ave Office");
-                *self.subaddress_id_mut() = None;
-                *self.subaddress_type_mut() = Some(SubaddressType::Office);
-            }
-        }
-        if comp == "BEAVILLA VIEW" {
-            trace!("Fixing Beavilla View");
-            *self.street_name_mut() = "BEAVILLA".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::VIEW);
-        }
-        if comp == "COLUMBIA CREST" {
-            trace!("Fixing Columbia Crest");
-            *self.street_name_mut() = "COLUMBIA".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::CREST);
-        }
-        if comp == "HILLTOP VIEW" {
-            trace!("Fixing Hilltop View");
-            *self.street_name_mut() = "HILLTOP".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::VIEW);
-        }
-        if comp == "TENNESSEE VIEW" {
-            trace!("Fixing Tennessee View");
-            *self.street_name_mut() = "TENNESSEE".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::VIEW);
-        }
-        if comp == "MARILEE ROW" {
-            trace!("Fixing Marilee Row");
-            *self.street_name_mut() = "MARILEE".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::ROW);
-        }
-        if comp == "MEADOW GLEN" {
-            trace!("Fixing Meadow Glen");
-            *self.street_name_mut() = "MEADOW".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::GLEN);
-        }
-        if comp == "GENVERNA GLEN" {
-            trace!("Fixing Genverna Glen");
-            *self.street_name_mut() = "GENVERNA".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::GLEN);
-        }
-        if comp == "ROBERTSON CREST" {
-            trace!("Fixing Robertson Crest");
-            *self.street_name_mut() = "ROBERTSON".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::CREST);
-        }
-        if comp == "QUAIL CROSSING" {
-            trace!("Fixing Quail Crossing");
-            *self.street_name_mut() = "QUAIL".to_string();
-            *self.street_type_mut() = Some(StreetNamePostType::CROSSING);
-        }
-        if comp == "SIDE ROAD" && *self.directional() == Some(StreetNamePreDirectional::WEST) {
-            trace!("Fixing West Side Road");
-            *self.directional_mut() = None;
-            *self.street_name_mut() = "WEST SIDE".to_string();
-        }
-        if comp == "SOUTH SHORE DRIVE"
-            && *self.directional() == Some(StreetNamePreDirectional::SOUTH)
-        {
-            trace!("Fixing South Shore Drive");
-            *self.directional_mut() = None;
-            *self.street_name_mut() = "SOUTH SHORE".to_string();
-        }
+    /// The `standardize_with` method rewrites the components of the address using the rule set
+    /// `rules`.
+    #[tracing::instrument(skip_all)]
+    fn standardize_with(&mut self, rules: &Rules) {
+        rules.apply(self);
+    }
+}
 
-        if let Some(comp) = self.subaddress_id().clone() {
-            trace!("Fixing Laundry");
-            if comp == "LAUNDRY" {
-                *self.subaddress_id_mut() = None;
-                *self.subaddress_type_mut() = Some(SubaddressType::Laundry);
-            }
-            trace!("Fixing Office");
-            if comp == "OFFICE" {
-                *self.subaddress_id_mut() = None;
-                *self.subaddress_type_mut() = Some(SubaddressType::Office);
-            }
-            trace!("Fixing Rec");
-            if comp == "REC" {
-                *self.subaddress_id_mut() = None;
-                *self.subaddress_type_mut() = Some(SubaddressType::Rec);
-            }
-            trace!("Fixing Trailer");
-            if comp == "TRLR" {
-                *self.subaddress_id_mut() = None;
-                *self.subaddress_type_mut() = Some(SubaddressType::Trailer);
-            }
-            trace!("Fixing Floor 4");
-            if comp == "FLOOR 4" {
-                *self.subaddress_id_mut() = Some("4".to_string());
-                *self.subaddress_type_mut() = Some(SubaddressType::Floor);
-            }
-            if comp.contains("APT") {
-                let (_, id) =
-                    tag::<&str, &str, nom::error::Error<_>>("APT")(comp.as_str()).unwrap();
-                *self.subaddress_id_mut() = Some(id.to_string());
-            }
-            if comp.contains("RV") {
-                let (_, id) = tag::<&str, &str, nom::error::Error<_>>("RV")(comp.as_str()).unwrap();
-                *self.subaddress_id_mut() = Some(id.to_string());
+/// The `address_label` function returns the label of `address`, using `complete_street_name` as
+/// the complete street name.  See [`Address::label`].
+fn address_label<T: Address + ?Sized>(address: &T, complete_street_name: &str) -> String {
+    let complete_address_number = match &address.number_suffix() {
+        Some(suffix) => format!("{} {}", address.number(), suffix),
+        None => address.number().to_string(),
+    };
+
+    tracing::trace!("Street name: {complete_street_name}");
+
+    let accessory = address.building().as_ref().map(|v| format!("BLDG {v}"));
+
+    let complete_subaddress = match &address.subaddress_id() {
+        Some(identifier) => match address.subaddress_type() {
+            Some(subaddress_type) => {
+                Some(format!("{} {}", subaddress_type.abbreviate(), identifier))
             }
-            if comp.contains("CABIN") {
-                let (_, id) =
-                    tag::<&str, &str, nom::error::Error<_>>("CABIN")(comp.as_str()).unwrap();
-                *self.subaddress_id_mut() = Some(id.to_string());
+            None => Some(format!("#{}", identifier)),
+        },
+        None => address
+            .subaddress_type()
+            .map(|subaddress_type| subaddress_type.abbreviate()),
+    };
+
+    match complete_subaddress {
+        Some(subaddress) => format!(
+            "{} {} {}",
+            complete_address_number, complete_street_name, subaddress
+        ),
+        None => match accessory {
+            Some(value) => format!(
+                "{} {} {}",
+                complete_address_number, complete_street_name, value
+            ),
+            None => format!("{} {}", complete_address_number, complete_street_name),
+        },
+    }
+}
+
+/// The `complete_street_name` function returns the complete street name of `address`, using
+/// `street_name` as the street name.  See [`Address::complete_street_name`].
+fn complete_street_name<T: Address + ?Sized>(
+    address: &T,
+    abbreviate: bool,
+    street_name: &str,
+) -> String {
+    let mut name = String::new();
+    if let Some(directional) = address.directional() {
+        if abbreviate {
+            if let Some(dir) = &address.directional_abbreviated() {
+                name.push_str(dir);
             }
+        } else {
+            name.push_str(&directional.to_string());
+        }
+        name.push(' ');
+    }
+    if let Some(modifier) = address.street_name_pre_modifier() {
+        name.push_str(modifier.upper().as_str());
+        name.push(' ');
+    }
+    if let Some(pre_type) = address.street_name_pre_type() {
+        name.push_str(pre_type.upper().as_str());
+        name.push(' ');
+    }
+    if let Some(separator) = address.street_name_separator() {
+        name.push_str(separator.upper().as_str());
+        name.push(' ');
+    }
+    name.push_str(street_name);
+    if let Some(post_type) = address.street_type() {
+        tracing::trace!("Post type found: {post_type}");
+        name.push(' ');
+        if abbreviate {
+            tracing::trace!("Abbreviated: {}", post_type.abbreviate());
+            name.push_str(&post_type.abbreviate());
+        } else {
+            name.push_str(&post_type.to_string());
+        }
+    } else {
+        tracing::trace!("Post type not found for {name}.");
+    }
+    if let Some(post_directional) = address.street_name_post_directional() {
+        name.push(' ');
+        if abbreviate {
+            name.push_str(&post_directional.abbreviate());
+        } else {
+            name.push_str(&post_directional.to_string());
         }
     }
+    name
 }
 
 /// The `Addresses` trait enables methods that act on vectors of type [`Address`].
@@ -454,6 +516,13 @@ where
                     tracing::info!("Could not parse post type.")
                 }
             }
+            "post_directional" => {
+                if let Ok((_, dir)) = Parse::post_directional(field) {
+                    self.retain(|r| r.street_name_post_directional() == &dir)
+                } else {
+                    tracing::info!("Could not parse post directional.")
+                }
+            }
             "status" => self.retain(|r| r.status().to_string() == field),
             _ => info!("Invalid filter provided."),
         }
@@ -495,53 +564,11 @@ where
     }
 
     /// The `citify` method takes county address naming conventions and converts them to city
-    /// naming conventions.
+    /// naming conventions, applying the rule set from [`Rules::citify_rules`].
     #[tracing::instrument(skip_all)]
     fn citify(&mut self) {
         trace!("Running Citify");
-        for address in self.iter_mut() {
-            let comp_street = address.complete_street_name(false);
-            if comp_street == "NE BEAVILLA VIEW" {
-                trace!("Fixing Beavilla View");
-                *address.street_name_mut() = "BEAVILLA".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::VIEW);
-            }
-            if comp_street == "COLUMBIA CREST" {
-                trace!("Fixing Columbia Crest");
-                *address.street_name_mut() = "COLUMBIA".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::CREST);
-            }
-            if comp_street == "SE FORMOSA GARDENS" {
-                trace!("Fixing Formosa Gardens");
-                *address.street_name_mut() = "FORMOSA".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::GARDENS);
-            }
-            if comp_street == "SE HILLTOP VIEW" {
-                trace!("Fixing Hilltop View");
-                *address.street_name_mut() = "HILLTOP".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::VIEW);
-            }
-            if comp_street == "MARILEE ROW" {
-                trace!("Fixing Marilee Row");
-                *address.street_name_mut() = "MARILEE".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::ROW);
-            }
-            if comp_street == "MEADOW GLEN" {
-                trace!("Fixing Meadow Glen");
-                *address.street_name_mut() = "MEADOW".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::GLEN);
-            }
-            if comp_street == "ROBERTSON CREST" {
-                trace!("Fixing Robertson Crest");
-                *address.street_name_mut() = "ROBERTSON".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::CREST);
-            }
-            if comp_street == "NE QUAIL CROSSING" {
-                trace!("Fixing Quail Crossing");
-                *address.street_name_mut() = "QUAIL".to_string();
-                *address.street_type_mut() = Some(StreetNamePostType::CROSSING);
-            }
-        }
+        self.standardize_with(Rules::citify_rules());
     }
 
     /// The `LexisNexis` method produces the LexisNexis table showing dispatch jurisdiction for
@@ -556,7 +583,20 @@ where
     #[tracing::instrument(skip_all)]
     fn standardize(&mut self) {
         trace!("Running standardize");
-        self.iter_mut().map(|v| v.standardize()).for_each(drop);
+        self.standardize_with(Rules::default_rules());
+    }
+
+    /// The `standardize_with` method rewrites the components of each address using the rule set
+    /// `rules`.
+    #[tracing::instrument(skip_all)]
+    fn standardize_with(&mut self, rules: &Rules) {
+        trace!("Applying {} rules", rules.len());
+        let applied = self.iter_mut().map(|v| rules.apply(v)).sum::<usize>();
+        tracing::info!(
+            "Applied {} standardization rules to {} addresses.",
+            applied,
+            self.len()
+        );
     }
 }
 
@@ -598,6 +638,9 @@ pub struct CommonAddress {
     /// The `street_type` field represents the street name post type component of the complete street
     /// name.
     pub street_type: Option<StreetNamePostType>,
+    /// The `post_directional` field represents the street name post directional component of the
+    /// complete street name.
+    pub post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     pub subaddress_type: Option<SubaddressType>,
@@ -685,6 +728,14 @@ impl Address for CommonAddress {
         &mut self.street_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_id
     }
@@ -760,6 +811,7 @@ impl<T: Address> From<&T> for CommonAddress {
         let separator = *address.street_name_separator();
         let street_name = address.street_name().clone();
         let street_type = *address.street_type();
+        let post_directional = *address.street_name_post_directional();
         let subaddress_type = *address.subaddress_type();
         let subaddress_id = address.subaddress_id().clone();
         let floor = *address.floor();
@@ -777,6 +829,7 @@ impl<T: Address> From<&T> for CommonAddress {
             separator,
             street_name,
             street_type,
+            post_directional,
             subaddress_type,
             subaddress_id,
             floor,
@@ -844,6 +897,10 @@ impl IntoCsv<CommonAddresses> for CommonAddresses {
     }
 }
 
+/// The `MAX_UNIT_RANGE` constant limits the number of units expanded from a numeric unit range,
+/// so that identifiers like "1-9999" read as a single unit rather than thousands.
+const MAX_UNIT_RANGE: u64 = 500;
+
 /// The `PartialAddress` struct contains optional fields so that incomplete or missing data can be
 /// compared against [`Addresses`] or [`PartialAddresses`] for potential matches.  Used to help
 /// match address information that does not parse into a full valid address.
@@ -863,6 +920,10 @@ pub struct PartialAddress {
     /// The `address_number` field represents the address number component of the complete address
     /// number.
     pub address_number: Option<i64>,
+    /// The `address_number_to` field represents the upper bound of an address range, such as the
+    /// 110 in "100-110 MAIN ST", with `address_number` holding the lower bound.
+    #[serde(default)]
+    pub address_number_to: Option<i64>,
     /// The `number_suffix` field represents the address number suffix component of the complete
     /// address number.
     pub address_number_suffix: Option<String>,
@@ -883,6 +944,9 @@ pub struct PartialAddress {
     /// The `street_type` field represents the street name post type component of the complete street
     /// name.
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     pub subaddress_type: Option<SubaddressType>,
@@ -903,6 +967,18 @@ pub struct PartialAddress {
     /// The `status` field represents the local status of the address as determined by the relevant
     /// addressing authority.
     pub status: Option<AddressStatus>,
+    /// The `postal_delivery_type` field represents the class of a non-physical mailing address,
+    /// such as a PO box, rural route or general delivery.  `None` for addresses with a physical
+    /// location.
+    #[serde(default)]
+    pub postal_delivery_type: Option<PostalDeliveryType>,
+    /// The `postal_delivery_route` field represents the route number of a rural route or highway
+    /// contract address.
+    #[serde(default)]
+    pub postal_delivery_route: Option<String>,
+    /// The `postal_delivery_box` field represents the box identifier of a mailing address.
+    #[serde(default)]
+    pub postal_delivery_box: Option<String>,
 }
 
 impl PartialAddress {
@@ -917,6 +993,39 @@ impl PartialAddress {
         self.address_number
     }
 
+    /// The `address_number_to` field represents the upper bound of an address range.  This
+    /// function returns the value of the field.
+    pub fn address_number_to(&self) -> Option<i64> {
+        self.address_number_to
+    }
+
+    /// The `address_number_range` method returns the range of address numbers covered by the
+    /// partial address, if the address number reads as a range like "100-110".
+    pub fn address_number_range(&self) -> Option<AddressNumberRange> {
+        match (self.address_number, self.address_number_to) {
+            (Some(from), Some(to)) => AddressNumberRange::new(from, to),
+            _ => None,
+        }
+    }
+
+    /// The `address_numbers` method returns the address numbers covered by the partial address:
+    /// each number in the range if the address number reads as a range, or the address number.
+    /// A range spanning more than [`AddressNumberRange::MAX_SPAN`] returns only the lower bound.
+    /// To test whether a number falls within the range, use [`AddressNumberRange::contains`].
+    pub fn address_numbers(&self) -> Vec<i64> {
+        match self
+            .address_number_range()
+            .map(|range| (range, range.numbers()))
+        {
+            Some((_, Some(numbers))) => numbers.collect(),
+            Some((range, None)) => {
+                tracing::warn!("Address range {range} is too wide to expand.");
+                vec![range.from]
+            }
+            None => self.address_number.into_iter().collect(),
+        }
+    }
+
     /// The `address_number_suffix` field represents the address number suffix component of the
     /// complete address number.  This function returns the cloned value of the field.
     pub fn address_number_suffix(&self) -> Option<String> {
@@ -959,6 +1068,12 @@ impl PartialAddress {
         self.street_name_post_type
     }
 
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.  This function returns the cloned value of the field.
+    pub fn street_name_post_directional(&self) -> Option<StreetNamePreDirectional> {
+        self.street_name_post_directional
+    }
+
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.  This function returns the cloned value of the field.
     pub fn subaddress_type(&self) -> Option<SubaddressType> {
@@ -971,6 +1086,35 @@ impl PartialAddress {
         self.subaddress_identifier.clone()
     }
 
+    /// The `subaddress_identifiers` method returns the units covered by the subaddress identifier.
+    /// A unit range such as "100-104" or "A-C" expands into each unit in the range, while any
+    /// other identifier returns as the only unit.  Returns an empty vector if the subaddress
+    /// identifier is not present.
+    pub fn subaddress_identifiers(&self) -> Vec<String> {
+        let Some(identifier) = &self.subaddress_identifier else {
+            return Vec::new();
+        };
+        if let Some((from, to)) = identifier.split_once('-') {
+            if let (Ok(from), Ok(to)) = (from.parse::<u64>(), to.parse::<u64>()) {
+                if from < to && to - from <= MAX_UNIT_RANGE {
+                    return (from..=to).map(|unit| unit.to_string()).collect();
+                }
+            }
+            let letter = |s: &str| match s.as_bytes() {
+                [c] if c.is_ascii_alphabetic() => Some(c.to_ascii_uppercase()),
+                _ => None,
+            };
+            if let (Some(from), Some(to)) = (letter(from), letter(to)) {
+                if from < to {
+                    return (from..=to)
+                        .map(|unit| char::from(unit).to_string())
+                        .collect();
+                }
+            }
+        }
+        vec![identifier.clone()]
+    }
+
     /// The `building` field represents the unique identifier for a building.  This function
     /// returns the cloned value of the field.
     pub fn building(&self) -> Option<String> {
@@ -987,6 +1131,11 @@ impl PartialAddress {
         self.address_number = Some(value);
     }
 
+    /// Sets the value of the `address_number_to` field to `value`.
+    pub fn set_address_number_to(&mut self, value: Option<i64>) {
+        self.address_number_to = value;
+    }
+
     /// Sets the value of the `address_number_suffix` field to Some(`value`).
     pub fn set_address_number_suffix(&mut self, value: Option<&str>) {
         if let Some(suffix) = value {
@@ -1011,6 +1160,11 @@ impl PartialAddress {
         self.street_name_post_type = Some(value.to_owned());
     }
 
+    /// Sets the value of the `street_name_post_directional` field to Some(`value`).
+    pub fn set_post_directional(&mut self, value: &StreetNamePreDirectional) {
+        self.street_name_post_directional = Some(value.to_owned());
+    }
+
     /// Sets the value of the `subaddress_type` field to Some(`value`).
     pub fn set_subaddress_type(&mut self, value: &SubaddressType) {
         self.subaddress_type = Some(value.to_owned());
@@ -1021,13 +1175,61 @@ impl PartialAddress {
         self.subaddress_identifier = Some(value.to_owned());
     }
 
+    /// Sets the value of the `building` field to Some(`value`).
+    pub fn set_building(&mut self, value: &str) {
+        self.building = Some(value.to_owned());
+    }
+
+    /// Sets the value of the `floor` field to Some(`value`).
+    pub fn set_floor(&mut self, value: i64) {
+        self.floor = Some(value);
+    }
+
+    /// The `postal_delivery_type` field represents the class of a non-physical mailing address.
+    /// This function returns the value of the field.
+    pub fn postal_delivery_type(&self) -> Option<PostalDeliveryType> {
+        self.postal_delivery_type
+    }
+
+    /// The `postal_delivery` method returns the components of a non-physical mailing address, or
+    /// `None` if the address has a physical location.
+    pub fn postal_delivery(&self) -> Option<PostalDelivery> {
+        self.postal_delivery_type
+            .map(|delivery_type| PostalDelivery {
+                delivery_type,
+                route: self.postal_delivery_route.clone(),
+                box_id: self.postal_delivery_box.clone(),
+            })
+    }
+
+    /// Sets the values of the `postal_delivery_type`, `postal_delivery_route` and
+    /// `postal_delivery_box` fields from `value`.
+    pub fn set_postal_delivery(&mut self, value: Option<PostalDelivery>) {
+        self.postal_delivery_type = value.as_ref().map(|v| v.delivery_type);
+        self.postal_delivery_route = value.as_ref().and_then(|v| v.route.clone());
+        self.postal_delivery_box = value.and_then(|v| v.box_id);
+    }
+
+    /// The `is_physical` method returns `false` if the address is a non-physical mailing address,
+    /// such as a PO box, which cannot match an address with a physical location.
+    pub fn is_physical(&self) -> bool {
+        self.postal_delivery_type.is_none()
+    }
+
     /// Returns a String representing the address label, consisting of the complete address number,
     /// complete street name and complete subaddress, used to produce map or mailing labels.
     pub fn label(&self) -> String {
         let mut address = "".to_owned();
+        if let Some(delivery) = self.postal_delivery() {
+            address.push_str(&delivery.label());
+        }
         if let Some(address_number) = self.address_number() {
             address.push_str(&address_number.to_string());
         }
+        if let Some(to) = self.address_number_to() {
+            address.push('-');
+            address.push_str(&to.to_string());
+        }
         if let Some(address_number_suffix) = self.address_number_suffix() {
             address.push(' ');
             address.push_str(&address_number_suffix);
@@ -1056,6 +1258,19 @@ impl PartialAddress {
             address.push(' ');
             address.push_str(&post_type.abbreviate());
         }
+        if let Some(post_directional) = self.street_name_post_directional() {
+            address.push(' ');
+            address.push_str(&post_directional.abbreviate());
+        }
+        if let Some(building) = self.building() {
+            address.push_str(&format!(
+                " {} {building}",
+                SubaddressType::Building.abbreviate()
+            ));
+        }
+        if let Some(floor) = self.floor() {
+            address.push_str(&format!(" {} {floor}", SubaddressType::Floor.abbreviate()));
+        }
         let subtype_flag;
         if let Some(subtype) = self.subaddress_type() {
             subtype_flag = true;
@@ -1098,9 +1313,16 @@ impl PartialAddress {
     /// representation.
     pub fn complete_address(&self) -> String {
         let mut address = "".to_owned();
+        if let Some(delivery) = self.postal_delivery() {
+            address.push_str(&delivery.label());
+        }
         if let Some(address_number) = self.address_number() {
             address.push_str(&format!("{}", address_number));
         }
+        if let Some(to) = self.address_number_to() {
+            address.push('-');
+            address.push_str(&to.to_string());
+        }
         if let Some(address_number_suffix) = self.address_number_suffix() {
             address.push(' ');
             address.push_str(&address_number_suffix);
@@ -1129,6 +1351,16 @@ impl PartialAddress {
             address.push(' ');
             address.push_str(&format!("{post_type}"));
         }
+        if let Some(post_directional) = self.street_name_post_directional() {
+            address.push(' ');
+            address.push_str(&format!("{post_directional}"));
+        }
+        if let Some(building) = self.building() {
+            address.push_str(&format!(" {} {building}", SubaddressType::Building.upper()));
+        }
+        if let Some(floor) = self.floor() {
+            address.push_str(&format!(" {} {floor}", SubaddressType::Floor.upper()));
+        }
         if let Some(subtype) = self.subaddress_type() {
             address.push(' ');
             address.push_str(&subtype.to_string().to_uppercase());
@@ -1141,97 +1373,16 @@ impl PartialAddress {
     }
 
     /// The `standardize` method takes county address naming conventions and converts them to city
-    /// naming conventions.
+    /// naming conventions, applying the default rule set from [`Rules::default_rules`].
     pub fn standardize(&mut self) {
         tracing::trace!("Standardizing partial address.");
-        if let Some(comp) = self.street_name().clone() {
-            // if comp == "AZALEA DRIVE" {
-            //     trace!("Fixing Azalea Drive Cutoff");
-            //     self.set_street_name("AZALEA");
-            //     self.set_post_type(&StreetNamePostType::DriveCutoff);
-            // }
-            //
-            // if let Some(sub) = self.subaddress_identifier() {
-            //     if comp == "LEWIS" && sub == "OFFICE" {
-            //         info!("Fixing Lewis Ave Office");
-            //         self.subaddress_identifier = None;
-            //         self.set_subaddress_type(&SubaddressType::Office);
-            //     }
-            // }
-            // if comp == "BEAVILLA VIEW" {
-            //     trace!("Fixing Beavilla View");
-            //     self.set_street_name("BEAVILLA");
-            //     self.set_post_type(&StreetNamePostType::VIEW);
-            // }
-            // if comp == "COLUMBIA CREST" {
-            //     trace!("Fixing Columbia Crest");
-            //     self.set_street_name("COLUMBIA");
-            //     self.set_post_type(&StreetNamePostType::CREST);
-            // }
-            // if comp == "HILLTOP VIEW" {
-            //     trace!("Fixing Hilltop View");
-            //     self.set_street_name("HILLTOP");
-            //     self.set_post_type(&StreetNamePostType::VIEW);
-            // }
-            // if comp == "TENNESSEE VIEW" {
-            //     trace!("Fixing Tennessee View");
-            //     self.set_street_name("TENNESSEE");
-            //     self.set_post_type(&StreetNamePostType::VIEW);
-            // }
-            // if comp == "MARILEE ROW" {
-            //     trace!("Fixing Marilee Row");
-            //     self.set_street_name("MARILEE");
-            //     self.set_post_type(&StreetNamePostType::ROW);
-            // }
-            // if comp == "MEADOW GLEN" {
-            //     trace!("Fixing Meadow Glen");
-            //     self.set_street_name("MEADOW");
-            //     self.set_post_type(&StreetNamePostType::GLEN);
-            // }
-            // if comp == "GENVERNA GLEN" {
-            //     trace!("Fixing Genverna Glen");
-            //     self.set_street_name("GENVERNA");
-            //     self.set_post_type(&StreetNamePostType::GLEN);
-            // }
-            // if comp == "ROBERTSON CREST" {
-            //     trace!("Fixing Robertson Crest");
-            //     self.set_street_name("ROBERTSON");
-            //     self.set_post_type(&StreetNamePostType::CREST);
-            // }
-            // if comp == "QUAIL CROSSING" {
-            //     trace!("Fixing Quail Crossing");
-            //     self.set_street_name("QUAIL");
-            //     self.set_post_type(&StreetNamePostType::CROSSING);
-            // }
-            if comp == "SIDE"
-                && self.street_name_pre_directional() == Some(StreetNamePreDirectional::WEST)
-            {
-                trace!("Fixing West Side Road");
-                self.street_name_pre_directional = None;
-                self.set_street_name("WEST SIDE");
-            }
-            if comp == "SHORE"
-                && self.street_name_pre_directional() == Some(StreetNamePreDirectional::SOUTH)
-            {
-                trace!("Fixing South Shore Drive");
-                self.street_name_pre_directional = None;
-                self.set_street_name("SOUTH SHORE");
-            }
-        }
-        // trace!("Running standardize");
-        // if self.street_name_pre_directional() == Some(StreetNamePreDirectional::WEST)
-        //     && self.street_name() == Some("SIDE".to_string())
-        // {
-        //     self.street_name_pre_directional = None;
-        //     self.street_name = Some("WEST SIDE".to_string());
-        // }
-        // if self.street_name_pre_directional() == Some(StreetNamePreDirectional::WEST)
-        //     && self.street_name().is_none()
-        // {
-        //     tracing::info!("Fixing West Street");
-        //     self.street_name_pre_directional = None;
-        //     self.street_name = Some("WEST".to_string());
-        // }
+        Rules::default_rules().apply_partial(self);
+    }
+
+    /// The `standardize_with` method rewrites the components of the partial address using the
+    /// rule set `rules`.
+    pub fn standardize_with(&mut self, rules: &Rules) {
+        rules.apply_partial(self);
     }
 }
 
@@ -1388,3 +1539,15 @@ impl IntoCsv<AddressDeltas> for AddressDeltas {
         to_csv(&mut self.0, path.as_ref().into())
     }
 }
+
+/// [`AddressDelta::new`] stores the projected coordinates of the address in the `longitude` and
+/// `latitude` fields, so the geometry converts them from Web Mercator.
+impl IntoGeoJson for AddressDeltas {
+    fn to_geojson<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(
+            &self.0,
+            |d| Some(Projection::WebMercator.to_geographic(d.longitude, d.latitude)),
+            path,
+        )
+    }
+}
@@ -0,0 +1,73 @@
+//! The `address_number_range` module provides the `AddressNumberRange` struct, representing a
+//! hyphenated span of address numbers, such as "100-110", used by records that refer to a
+//! building or lot covering several addresses.
+
+/// The `AddressNumberRange` struct holds the lower bound `from` and the upper bound `to` of an
+/// inclusive range of address numbers.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct AddressNumberRange {
+    /// The `from` field holds the lower bound of the range.
+    pub from: i64,
+    /// The `to` field holds the upper bound of the range.
+    pub to: i64,
+}
+
+impl AddressNumberRange {
+    /// The `MAX_SPAN` constant limits the span of a range expanded by
+    /// [`AddressNumberRange::numbers`], so that a range like "1-99999" does not expand into
+    /// thousands of address numbers.
+    pub const MAX_SPAN: i64 = 500;
+
+    /// The `new` method creates a range spanning the address numbers `from` and `to`.  Returns
+    /// `None` if `to` is less than `from`, since a descending span like "1205-3" is not a range.
+    pub fn new(from: i64, to: i64) -> Option<Self> {
+        (from <= to).then_some(Self { from, to })
+    }
+
+    /// The `same_side` method returns `true` if the bounds of the range share parity, indicating
+    /// the range covers one side of the street.
+    pub fn same_side(&self) -> bool {
+        (self.from - self.to) % 2 == 0
+    }
+
+    /// The `contains` method returns `true` if `number` falls within the range.  When the bounds
+    /// share parity, only numbers on the same side of the street fall within the range, so
+    /// "100-110" contains 104 but not 105.
+    pub fn contains(&self, number: i64) -> bool {
+        number >= self.from
+            && number <= self.to
+            && (!self.same_side() || (number - self.from) % 2 == 0)
+    }
+
+    /// The `numbers` method returns the address numbers within the range, as defined by
+    /// [`AddressNumberRange::contains`].  Returns `None` if the range spans more than
+    /// [`AddressNumberRange::MAX_SPAN`]; prefer [`AddressNumberRange::contains`] to test
+    /// membership.
+    pub fn numbers(&self) -> Option<impl Iterator<Item = i64>> {
+        let step = if self.same_side() { 2 } else { 1 };
+        (self.to - self.from <= Self::MAX_SPAN).then(|| (self.from..=self.to).step_by(step))
+    }
+
+    /// The `label` method returns the range in hyphenated form, e.g. "100-110".
+    pub fn label(&self) -> String {
+        format!("{}-{}", self.from, self.to)
+    }
+}
+
+impl std::fmt::Display for AddressNumberRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
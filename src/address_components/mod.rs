@@ -1,9 +1,12 @@
 //! The `address_components` module holds type definitions and methods for address component
 //! elements, as defined by FGDC guidelines.
+mod address_number_range;
 mod address_status;
 mod floor;
 mod postal_community;
+mod postal_delivery;
 mod state;
+mod street_name_ordinal;
 mod street_name_post_type;
 mod street_name_pre_directional;
 mod street_name_pre_modifier;
@@ -11,10 +14,13 @@ mod street_name_pre_type;
 mod street_separator;
 mod subaddress_type;
 
+pub use address_number_range::AddressNumberRange;
 pub use address_status::AddressStatus;
 pub use floor::zero_floor;
 pub use postal_community::PostalCommunity;
+pub use postal_delivery::{PostalDelivery, PostalDeliveryType};
 pub use state::State;
+pub use street_name_ordinal::OrdinalStyle;
 pub use street_name_post_type::StreetNamePostType;
 pub use street_name_pre_directional::StreetNamePreDirectional;
 pub use street_name_pre_modifier::StreetNamePreModifier;
@@ -51,6 +51,16 @@ impl PostalCommunity {
         title.to_uppercase()
     }
 
+    /// The `zip_codes` method returns the postal zip codes serving the community, including the
+    /// zip codes reserved for post office boxes.
+    pub fn zip_codes(&self) -> &'static [i64] {
+        match self {
+            Self::GrantsPass => &[97526, 97527, 97528],
+            Self::Medford => &[97501, 97504],
+            Self::Merlin => &[97532],
+        }
+    }
+
     /// The `match_mixed` method attempts to match the string `input` against a variant of
     /// `PostalCommunity`.  Used to parse the postal community from an address blob.
     ///
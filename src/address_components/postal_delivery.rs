@@ -0,0 +1,114 @@
+//! The `postal_delivery` module holds types for mailing addresses that do not describe a physical
+//! location, such as post office boxes, rural routes and general delivery.
+use serde::de::Deserializer;
+
+/// The `PostalDeliveryType` enum designates the class of a non-physical mailing address, following
+/// the USPS Postal Addressing Standards (Publication 28) as adopted by the FGDC address standard.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+    derive_more::FromStr,
+    strum::EnumIter,
+)]
+pub enum PostalDeliveryType {
+    /// A post office box, e.g. "PO BOX 123".
+    PoBox,
+    /// A rural route box, e.g. "RR 2 BOX 152".
+    RuralRoute,
+    /// A highway contract route box, e.g. "HC 68 BOX 23A".
+    HighwayContract,
+    /// Mail held for pickup at the post office, e.g. "GENERAL DELIVERY".
+    GeneralDelivery,
+}
+
+impl PostalDeliveryType {
+    /// The `abbreviate` method returns the USPS designator for the delivery type.
+    pub fn abbreviate(&self) -> String {
+        match self {
+            Self::PoBox => "PO BOX",
+            Self::RuralRoute => "RR",
+            Self::HighwayContract => "HC",
+            Self::GeneralDelivery => "GENERAL DELIVERY",
+        }
+        .to_string()
+    }
+
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `PostalDeliveryType`, accepting the variant name, the USPS designator and common
+    /// alternative spellings.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        let normalized = input
+            .to_uppercase()
+            .replace('.', "")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        match normalized.as_str() {
+            "POBOX" | "PO BOX" | "P O BOX" | "POB" | "PO" | "POST OFFICE BOX" | "BOX" => {
+                Some(Self::PoBox)
+            }
+            "RURALROUTE" | "RR" | "R R" | "RURAL ROUTE" | "RFD" | "RURAL FREE DELIVERY" => {
+                Some(Self::RuralRoute)
+            }
+            "HIGHWAYCONTRACT"
+            | "HC"
+            | "HCR"
+            | "HIGHWAY CONTRACT"
+            | "HIGHWAY CONTRACT ROUTE"
+            | "STAR ROUTE" => Some(Self::HighwayContract),
+            "GENERALDELIVERY" | "GENERAL DELIVERY" | "GEN DEL" => Some(Self::GeneralDelivery),
+            _ => None,
+        }
+    }
+
+    /// Deserialization function for postal delivery types.  Values that do not match a
+    /// designator deserialize to `None`.
+    pub fn deserialize_mixed<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Self>, D::Error> {
+        let intermediate: Option<&str> = serde::Deserialize::deserialize(de)?;
+        Ok(intermediate.and_then(Self::match_mixed))
+    }
+}
+
+/// The `PostalDelivery` struct holds the components of a non-physical mailing address, as read by
+/// [`crate::Parse::postal_delivery`].
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub struct PostalDelivery {
+    /// The `delivery_type` field holds the class of the mailing address.
+    pub delivery_type: PostalDeliveryType,
+    /// The `route` field holds the route number of a rural route or highway contract address.
+    pub route: Option<String>,
+    /// The `box_id` field holds the box identifier, if present.
+    pub box_id: Option<String>,
+}
+
+impl PostalDelivery {
+    /// The `label` method returns the mailing address in USPS format, e.g. "PO BOX 123" or
+    /// "RR 2 BOX 152".
+    pub fn label(&self) -> String {
+        let mut label = self.delivery_type.abbreviate();
+        if let Some(route) = &self.route {
+            label.push(' ');
+            label.push_str(route);
+        }
+        if let Some(box_id) = &self.box_id {
+            if self.delivery_type != PostalDeliveryType::PoBox {
+                label.push_str(" BOX");
+            }
+            label.push(' ');
+            label.push_str(box_id);
+        }
+        label
+    }
+}
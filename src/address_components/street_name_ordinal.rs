@@ -0,0 +1,183 @@
+//! The `street_name_ordinal` module normalizes numbered street names, which sources write as
+//! "6TH", "SIXTH" or "6", to a canonical form.
+
+/// Spelled-out ordinals from one to nineteen, indexed from zero.
+const UNITS: [&str; 19] = [
+    "FIRST",
+    "SECOND",
+    "THIRD",
+    "FOURTH",
+    "FIFTH",
+    "SIXTH",
+    "SEVENTH",
+    "EIGHTH",
+    "NINTH",
+    "TENTH",
+    "ELEVENTH",
+    "TWELFTH",
+    "THIRTEENTH",
+    "FOURTEENTH",
+    "FIFTEENTH",
+    "SIXTEENTH",
+    "SEVENTEENTH",
+    "EIGHTEENTH",
+    "NINETEENTH",
+];
+
+/// Cardinal and ordinal forms of the tens from twenty to ninety, indexed from zero.
+const TENS: [(&str, &str); 8] = [
+    ("TWENTY", "TWENTIETH"),
+    ("THIRTY", "THIRTIETH"),
+    ("FORTY", "FORTIETH"),
+    ("FIFTY", "FIFTIETH"),
+    ("SIXTY", "SIXTIETH"),
+    ("SEVENTY", "SEVENTIETH"),
+    ("EIGHTY", "EIGHTIETH"),
+    ("NINETY", "NINETIETH"),
+];
+
+/// The `OrdinalStyle` enum designates how numbered street names print.  Street names read from
+/// addresses and import files convert to the [`OrdinalStyle::Numeric`] form, the canonical form
+/// used for comparison.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+    derive_more::FromStr,
+    strum::EnumIter,
+)]
+pub enum OrdinalStyle {
+    /// Ordinals print as numbers with a suffix, e.g. "6TH".
+    #[default]
+    Numeric,
+    /// Ordinals print as words, e.g. "SIXTH".  Ordinals above ninety-nine print as numbers.
+    Spelled,
+}
+
+impl OrdinalStyle {
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `OrdinalStyle`.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "numeric" | "number" | "numbers" => Some(Self::Numeric),
+            "spelled" | "word" | "words" => Some(Self::Spelled),
+            _ => None,
+        }
+    }
+
+    /// The `format` method rewrites the ordinals in `street_name` in the style of `self`, so that
+    /// "TWENTY-FIRST" reads "21ST" in the numeric style and "21ST" reads "TWENTY-FIRST" in the
+    /// spelled style.  Other words are unchanged.
+    pub fn format(&self, street_name: &str) -> String {
+        let words = street_name.split_whitespace().collect::<Vec<&str>>();
+        let mut formatted = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            // Compound ordinals may span two words, as in "TWENTY FIRST".
+            let pair = words
+                .get(i + 1)
+                .and_then(|next| Self::ordinal(&format!("{}-{}", words[i], next)));
+            let (value, width) = match pair {
+                Some(value) => (Some(value), 2),
+                None => (Self::ordinal(words[i]), 1),
+            };
+            match value {
+                Some(value) => formatted.push(self.print(value)),
+                None => formatted.push(words[i].to_string()),
+            }
+            i += width;
+        }
+        formatted.join(" ")
+    }
+
+    /// The `canonical` method returns `street_name` with ordinals in the numeric style, the form
+    /// used to compare street names.
+    pub fn canonical(street_name: &str) -> String {
+        Self::Numeric.format(street_name)
+    }
+
+    /// The `canonical_numbered` method returns the canonical form of `street_name`, also reading a
+    /// street name that is a bare number, like the "6" in "6 ST", as an ordinal.  Numbered highway
+    /// routes also have bare numbers for street names, so only use this method for streets known
+    /// to not be routes.
+    pub fn canonical_numbered(street_name: &str) -> String {
+        let name = street_name.trim();
+        match name.parse::<u32>() {
+            Ok(value) if value > 0 => Self::Numeric.print(value),
+            _ => Self::canonical(name),
+        }
+    }
+
+    /// The `is_ordinal` method returns `true` if `word` is an ordinal, spelled out like "SIXTH" or
+    /// "TWENTY-FIRST", or numeric like "6TH".
+    pub fn is_ordinal(word: &str) -> bool {
+        Self::ordinal(word).is_some()
+    }
+
+    /// The `ordinal` method returns the value of `word` if it is an ordinal, either spelled out
+    /// like "SIXTH" or "TWENTY-FIRST", or numeric like "6TH".  Numeric ordinals with the wrong
+    /// suffix, like "21TH", still read.
+    fn ordinal(word: &str) -> Option<u32> {
+        let word = word.to_uppercase();
+        let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+            let suffix = &word[digits.len()..];
+            return match suffix {
+                "ST" | "ND" | "RD" | "TH" => digits.parse().ok().filter(|value| *value > 0),
+                _ => None,
+            };
+        }
+        if word == "HUNDREDTH" {
+            return Some(100);
+        }
+        if let Some(position) = UNITS.iter().position(|unit| *unit == word) {
+            return Some(position as u32 + 1);
+        }
+        for (position, (cardinal, ordinal)) in TENS.iter().enumerate() {
+            let tens = (position as u32 + 2) * 10;
+            if word == *ordinal {
+                return Some(tens);
+            }
+            if let Some(unit) = word.strip_prefix(&format!("{cardinal}-")) {
+                if let Some(unit) = UNITS[..9].iter().position(|u| *u == unit) {
+                    return Some(tens + unit as u32 + 1);
+                }
+            }
+        }
+        None
+    }
+
+    /// The `print` method returns the ordinal `value` in the style of `self`.
+    fn print(&self, value: u32) -> String {
+        match self {
+            Self::Spelled if (1..20).contains(&value) => UNITS[value as usize - 1].to_string(),
+            Self::Spelled if (20..100).contains(&value) => {
+                let (cardinal, ordinal) = TENS[value as usize / 10 - 2];
+                match value % 10 {
+                    0 => ordinal.to_string(),
+                    unit => format!("{cardinal}-{}", UNITS[unit as usize - 1]),
+                }
+            }
+            _ => {
+                let suffix = match (value % 10, value % 100) {
+                    (_, 11..=13) => "TH",
+                    (1, _) => "ST",
+                    (2, _) => "ND",
+                    (3, _) => "RD",
+                    _ => "TH",
+                };
+                format!("{value}{suffix}")
+            }
+        }
+    }
+}
@@ -657,7 +657,7 @@ impl StreetNamePostType {
     /// Calls [`Self::match_mixed`].
     #[tracing::instrument(skip_all)]
     pub fn deserialize_mixed<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Self>, D::Error> {
-        let intermediate = serde::Deserialize::deserialize(de)?;
-        Ok(Self::match_mixed(intermediate))
+        let intermediate: Option<&str> = serde::Deserialize::deserialize(de)?;
+        Ok(intermediate.and_then(Self::match_mixed))
     }
 }
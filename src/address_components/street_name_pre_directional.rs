@@ -114,7 +114,7 @@ impl StreetNamePreDirectional {
     /// variants to the match statement.
     #[tracing::instrument(skip_all)]
     pub fn deserialize_mixed<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Self>, D::Error> {
-        let intermediate = serde::Deserialize::deserialize(de)?;
-        Ok(Self::match_mixed(intermediate))
+        let intermediate: Option<&str> = serde::Deserialize::deserialize(de)?;
+        Ok(intermediate.and_then(Self::match_mixed))
     }
 }
@@ -3,6 +3,7 @@
 //! types, because some valid post types are in use as street names, and we want "Park" and "Fall"
 //! to map to street names, while catching those existing cases in our area using pre types.  We do
 //! not issue addresses with pre types.
+use crate::OrdinalStyle;
 use convert_case::Casing;
 use serde::de::Deserializer;
 
@@ -31,6 +32,12 @@ pub enum StreetNamePreType {
     Highway,
     Interstate,
     Mount,
+    /// A numbered route of the U.S. highway system, e.g. "US HIGHWAY 199".
+    UsHighway,
+    /// A numbered route of the state highway system, e.g. "STATE HIGHWAY 238" or "OR-238".
+    StateHighway,
+    /// A numbered state route, e.g. "STATE ROUTE 238".
+    StateRoute,
 }
 
 impl StreetNamePreType {
@@ -51,33 +58,151 @@ impl StreetNamePreType {
             Self::Highway => "HIGHWAY",
             Self::Interstate => "INTERSTATE",
             Self::Mount => "MOUNT",
+            Self::UsHighway => "US HIGHWAY",
+            Self::StateHighway => "STATE HIGHWAY",
+            Self::StateRoute => "STATE ROUTE",
         };
         label.to_string()
     }
 
+    /// The `abbreviate` method returns the standard postal abbreviation for a street name pre
+    /// type, e.g. "HWY" for [`StreetNamePreType::Highway`].
+    pub fn abbreviate(&self) -> String {
+        let abbreviation = match self {
+            Self::Avenue => "AVE",
+            Self::Highway => "HWY",
+            Self::Interstate => "INTERSTATE",
+            Self::Mount => "MT",
+            Self::UsHighway => "US HWY",
+            Self::StateHighway => "STATE HWY",
+            Self::StateRoute => "STATE RTE",
+        };
+        abbreviation.to_string()
+    }
+
     /// The `match_mixed` method attempts to match the string `input` against a variant of
-    /// `StreetNamePreType`.
+    /// `StreetNamePreType`.  Highway designators may span several words, and periods and hyphens
+    /// are ignored, so "U.S. HWY", "US-" and "US HIGHWAY" all read as
+    /// [`StreetNamePreType::UsHighway`].
     #[tracing::instrument]
     pub fn match_mixed(input: &str) -> Option<Self> {
-        match input.to_lowercase().as_str() {
+        let normalized = input
+            .to_lowercase()
+            .replace(['.', '-'], " ")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        match normalized.as_str() {
             "avenue" => Some(Self::Avenue),
             "ave" => Some(Self::Avenue),
             // "fork" => Some(Self::Fork),
             "highway" => Some(Self::Highway),
             "hwy" => Some(Self::Highway),
-            "interstate" => Some(Self::Interstate),
+            "hy" => Some(Self::Highway),
+            "interstate" | "interstate highway" | "interstate hwy" | "i" | "ih" => {
+                Some(Self::Interstate)
+            }
             "mount" => Some(Self::Mount),
             "mt" => Some(Self::Mount),
+            "us highway" | "us hwy" | "u s highway" | "u s hwy" | "us" | "u s" | "us route"
+            | "ushighway" => Some(Self::UsHighway),
+            "state highway" | "state hwy" | "st highway" | "st hwy" | "oregon highway"
+            | "oregon hwy" | "or highway" | "or hwy" | "or" | "ore" | "oregon" | "statehighway" => {
+                Some(Self::StateHighway)
+            }
+            "state route" | "state rte" | "state rt" | "sr" | "stateroute" => {
+                Some(Self::StateRoute)
+            }
             _ => None,
         }
     }
 
+    /// The `is_route` method returns true if the pre type designates a numbered highway route.
+    pub fn is_route(&self) -> bool {
+        matches!(
+            self,
+            Self::Highway
+                | Self::Interstate
+                | Self::UsHighway
+                | Self::StateHighway
+                | Self::StateRoute
+        )
+    }
+
+    /// The `same_system` method returns true if the pre types can designate routes in the same
+    /// highway system.  A plain [`StreetNamePreType::Highway`] does not name the system, and
+    /// matches any route.
+    fn same_system(&self, other: &Self) -> bool {
+        let system = |pre_type: &Self| match pre_type {
+            Self::StateRoute => Self::StateHighway,
+            other => *other,
+        };
+        *self == Self::Highway || *other == Self::Highway || system(self) == system(other)
+    }
+
+    /// The `highway_route` method returns the highway pre type and route number of a street on a
+    /// numbered route, either read from the pre type and a street name like "199", or from a
+    /// street name holding the full route like "HIGHWAY 199" or "OR-238".  Returns `None` for
+    /// other streets.
+    pub fn highway_route(pre_type: Option<Self>, street_name: &str) -> Option<(Self, String)> {
+        let route_number = |name: &str| {
+            let name = name.trim();
+            (name.starts_with(|c: char| c.is_ascii_digit())
+                && name.len() <= 5
+                && name.chars().all(|c| c.is_ascii_alphanumeric()))
+            .then(|| name.to_uppercase())
+        };
+        if let Some(pre_type) = pre_type.filter(Self::is_route) {
+            if let Some(number) = route_number(street_name) {
+                return Some((pre_type, number));
+            }
+        }
+        match crate::Parse::pre_type(street_name) {
+            Ok((rem, Some(pre_type))) if pre_type.is_route() => {
+                route_number(rem).map(|number| (pre_type, number))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `same_street` method returns true if the pre types and street names of `a` and `b`
+    /// refer to the same street.  Numbered highway routes match by route number within the same
+    /// highway system, regardless of how the route is written, so "HWY 199", "US HIGHWAY 199" and
+    /// a street named "HIGHWAY 199" are the same street.  Named highways like "REDWOOD HWY" read
+    /// as a street name and post type, and do not match the numbered route they follow.  Other
+    /// streets match when the pre types
+    /// are equal and the street names are equal in the canonical form of
+    /// [`OrdinalStyle::canonical_numbered`], so "SIXTH", "6TH" and "6" are the same street.
+    pub fn same_street(a: (Option<Self>, &str), b: (Option<Self>, &str)) -> bool {
+        match (Self::highway_route(a.0, a.1), Self::highway_route(b.0, b.1)) {
+            (Some((a_type, a_number)), Some((b_type, b_number))) => {
+                a_number == b_number && a_type.same_system(&b_type)
+            }
+            _ => {
+                a.0 == b.0
+                    && (a.1 == b.1
+                        || OrdinalStyle::canonical_numbered(a.1)
+                            == OrdinalStyle::canonical_numbered(b.1))
+            }
+        }
+    }
+
+    /// The `street_key` method returns the street name used to look up `street_name` in an
+    /// index, being the route number for numbered highway routes and the canonical street name
+    /// for other streets, so that the different ways of writing a street share a key.
+    pub fn street_key(pre_type: Option<Self>, street_name: &str) -> String {
+        match Self::highway_route(pre_type, street_name) {
+            Some((_, number)) => number,
+            None => OrdinalStyle::canonical_numbered(street_name),
+        }
+    }
+
     /// The `deserialize_mixed` method attempts to match the input to a valid street name
     /// pre-modifier variant.
     #[tracing::instrument(skip_all)]
     pub fn deserialize_mixed<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Self>, D::Error> {
-        let intermediate = serde::Deserialize::deserialize(de)?;
-        Ok(Self::match_mixed(intermediate))
+        let intermediate: Option<&str> = serde::Deserialize::deserialize(de)?;
+        Ok(intermediate.and_then(Self::match_mixed))
     }
 }
 
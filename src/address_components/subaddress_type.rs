@@ -97,6 +97,43 @@ impl SubaddressType {
         str.to_uppercase()
     }
 
+    /// The `requires_identifier` method returns `true` if the subaddress type requires a
+    /// subaddress identifier.  Under USPS Publication 28, designators such as "BSMT", "FRNT" and
+    /// "REAR" stand alone, as do the local shared spaces [`SubaddressType::Rec`] and
+    /// [`SubaddressType::Laundry`].
+    pub fn requires_identifier(&self) -> bool {
+        !matches!(
+            self,
+            Self::Basement
+                | Self::Front
+                | Self::Lobby
+                | Self::Lower
+                | Self::Office
+                | Self::Penthouse
+                | Self::Rear
+                | Self::Side
+                | Self::Upper
+                | Self::Rec
+                | Self::Laundry
+        )
+    }
+
+    /// The `has_floors` method returns `false` if the subaddress type designates a space that is
+    /// not within a multi-story building, such as a mobile home space or a boat slip, where a
+    /// floor number does not apply.
+    pub fn has_floors(&self) -> bool {
+        !matches!(
+            self,
+            Self::Hanger
+                | Self::Lot
+                | Self::Pier
+                | Self::Slip
+                | Self::Space
+                | Self::Stop
+                | Self::Trailer
+        )
+    }
+
     /// Matches subaddress types in the
     /// data that observe the official postal contraction.  For subaddress types with a mix of abbreviations and
     /// alternative spellings, the `match_mixed` method will work better.
@@ -185,7 +222,7 @@ impl SubaddressType {
     /// `SubaddressType`.
     #[tracing::instrument(skip_all)]
     pub fn deserialize_mixed<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Self>, D::Error> {
-        let intermediate = serde::Deserialize::deserialize(de)?;
-        Ok(Self::match_mixed(intermediate))
+        let intermediate: Option<&str> = serde::Deserialize::deserialize(de)?;
+        Ok(intermediate.and_then(Self::match_mixed))
     }
 }
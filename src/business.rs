@@ -1,8 +1,9 @@
 //! The `business` module matches addresses associated with business licenses against a set of known [`Addresses`], producing a record of
 //! matching, divergent and missing addresses.
 use crate::{
-    deserialize_phone_number, from_csv, to_csv, Address, AddressErrorKind, Geographic, IntoCsv, Io,
-    MatchStatus, Nom, Parse, StreetNamePostType, StreetNamePreDirectional,
+    deserialize_phone_number, from_csv, to_csv, to_geojson, Address, AddressErrorKind,
+    AddressIndex, Geographic, IntoCsv, IntoGeoJson, Io, MatchStatus, Nom, Parse, PostalDelivery,
+    StreetNamePostType, StreetNamePreDirectional, StreetNamePreType,
 };
 use derive_more::{Deref, DerefMut};
 // use galileo::galileo_types::geo::GeoPoint;
@@ -94,16 +95,48 @@ impl BusinessMatchRecords {
     /// Matches the provided address associated with a business license against the addresses in
     /// `addresses`, creating a new `BusinessMatchRecords` struct containing the results.
     pub fn new<T: Address + Geographic>(business: &BusinessLicense, addresses: &[T]) -> Self {
+        Self::from_candidates(business, addresses)
+    }
+
+    /// The `indexed` method produces the same `BusinessMatchRecords` as
+    /// [`BusinessMatchRecords::new()`], but only compares the business address against the
+    /// candidates returned by `index`.  If the address number of the business does not parse as
+    /// a number, no address can match, and the method returns a missing record.
+    pub fn indexed<T: Address + Geographic>(
+        business: &BusinessLicense,
+        index: &AddressIndex<T>,
+    ) -> Self {
+        let (pre_type, street_name) = business.street();
+        let candidates = match business.number() {
+            Some(number) => index.candidates(number, pre_type, street_name),
+            None => Vec::new(),
+        };
+        Self::from_candidates(business, candidates)
+    }
+
+    /// The `from_candidates` method matches the business address against each address in
+    /// `candidates`, keeping the exact match if present, otherwise any divergent matches,
+    /// otherwise a missing record, or a non-physical record for a mailing address such as a PO
+    /// box.
+    fn from_candidates<'a, T: Address + Geographic + 'a>(
+        business: &BusinessLicense,
+        candidates: impl IntoIterator<Item = &'a T>,
+    ) -> Self {
         let mut records = Vec::new();
-        for address in addresses {
+        for address in candidates {
             let business_match = business.coincident(address);
             if let Some(record) = business_match {
                 records.push(record);
             }
         }
         if records.is_empty() {
+            let match_status = if business.is_physical() {
+                MatchStatus::Missing
+            } else {
+                MatchStatus::NonPhysical
+            };
             records.push(BusinessMatchRecord {
-                match_status: MatchStatus::Missing,
+                match_status,
                 business_address_label: business.label(),
                 company_name: business.company_name(),
                 contact_name: business.contact_name(),
@@ -140,6 +173,9 @@ impl BusinessMatchRecords {
         business: &BusinessLicense,
         address_list: &[&[T]],
     ) -> Self {
+        if !business.is_physical() {
+            return Self::from_candidates::<T>(business, []);
+        }
         let mut matching = Vec::new();
         let mut divergent = Vec::new();
         let mut missing = Vec::new();
@@ -167,18 +203,20 @@ impl BusinessMatchRecords {
 
     /// For each [`BusinessLicense`] object in `businesses`, this method creates a
     /// `BusinessMatchRecords` using the [`BusinessMatchRecords::new()`] method.  Match records
-    /// will include matching, divergent and missing records.
+    /// will include matching, divergent and missing records.  Builds an [`AddressIndex`] over
+    /// `addresses` once, and matches each business against the indexed candidates.
     pub fn compare<T: Address + Geographic + Send + Sync>(
         businesses: &BusinessLicenses,
         addresses: &[T],
     ) -> Self {
+        let index = AddressIndex::new(addresses);
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
         )
         .unwrap();
         let record = businesses
             .par_iter()
-            .map(|address| BusinessMatchRecords::new(address, addresses))
+            .map(|address| BusinessMatchRecords::indexed(address, &index))
             .progress_with_style(style)
             .collect::<Vec<BusinessMatchRecords>>();
         let mut records = Vec::new();
@@ -213,7 +251,8 @@ impl BusinessMatchRecords {
 
     /// The `filter` method filters the [`BusinessMatchRecord`] objects in the `records` field
     /// based upon the match status of the record.  The `filter` field accepts the values
-    /// "missing", "nonmissing", "divergent", "matching", "unique" and "multiple". The "unique"
+    /// "missing", "nonphysical", "nonmissing", "divergent", "matching", "unique" and "multiple".
+    /// The "nonphysical" option returns mailing addresses such as PO boxes.  The "unique"
     /// option returns records where the business name is unique.  The "multiple" options returns
     /// records where multiple licenses exist registered under the same business name. The "local"
     /// option returns records within Grants Pass or Merlin.
@@ -226,6 +265,7 @@ impl BusinessMatchRecords {
     pub fn filter(mut self, filter: &str) -> Self {
         match filter {
             "missing" => self.retain(|r| r.match_status == MatchStatus::Missing),
+            "nonphysical" => self.retain(|r| r.match_status == MatchStatus::NonPhysical),
             "nonmissing" => self.retain(|r| r.match_status != MatchStatus::Missing),
             "divergent" => self.retain(|r| r.match_status == MatchStatus::Divergent),
             "matching" => self.retain(|r| r.match_status == MatchStatus::Matching),
@@ -308,6 +348,16 @@ impl IntoCsv<BusinessMatchRecords> for BusinessMatchRecords {
     }
 }
 
+impl IntoGeoJson for BusinessMatchRecords {
+    fn to_geojson<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(
+            &self.0,
+            |r| r.address_longitude.zip(r.address_latitude),
+            path,
+        )
+    }
+}
+
 /// The `BusinessLicense` struct is designed to deserialize CSV data produced by querying the
 /// EnerGov SQL database for active business licenses.  If the structure of the SQL query changes,
 /// this function will need to change to match the resulting fields in the CSV.
@@ -341,6 +391,12 @@ pub struct BusinessLicense {
         deserialize_with = "StreetNamePostType::deserialize_mixed"
     )]
     street_name_post_type: Option<StreetNamePostType>,
+    #[serde(
+        rename(deserialize = "POSTDIRECTION"),
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        default
+    )]
+    street_name_post_directional: Option<StreetNamePreDirectional>,
     #[serde(
         rename(deserialize = "UNITORSUITE"),
         deserialize_with = "csv::invalid_option"
@@ -371,11 +427,15 @@ impl BusinessLicense {
                 }
             }
         }
-        let street_name = self.street_name.trim().to_string();
+        let (pre_type, street_name) = self.street();
         if self.address_number == address.complete_address_number()
             && self.street_name_pre_directional == *address.directional()
-            && street_name == *address.street_name()
+            && StreetNamePreType::same_street(
+                (pre_type, street_name),
+                (*address.street_name_pre_type(), address.street_name()),
+            )
             && self.street_name_post_type == *address.street_type()
+            && self.street_name_post_directional == *address.street_name_post_directional()
         // && self.postal_community == address.postal_community()
         // && self.state_name == address.state_name()
         {
@@ -408,6 +468,42 @@ impl BusinessLicense {
         business_match
     }
 
+    /// The `street` method returns the street name pre type and street name of the business
+    /// address.  Business licenses hold any pre type in the street name, as in "HWY 199", so the
+    /// pre type is read from the front of the street name.
+    fn street(&self) -> (Option<StreetNamePreType>, &str) {
+        let street_name = self.street_name.trim();
+        match Parse::pre_type(street_name) {
+            Ok((rem, Some(pre_type))) if !rem.trim().is_empty() => (Some(pre_type), rem.trim()),
+            _ => (None, street_name),
+        }
+    }
+
+    /// The `number` method returns the address number of the business address, being the leading
+    /// numeric portion of the `address_number` field, or `None` if it does not parse as a number.
+    pub fn number(&self) -> Option<i64> {
+        self.address_number
+            .split_whitespace()
+            .next()
+            .and_then(|number| number.parse().ok())
+    }
+
+    /// The `postal_delivery` method returns the components of the business address if it is a
+    /// non-physical mailing address, such as a PO box, read from the address number and street
+    /// name fields.
+    pub fn postal_delivery(&self) -> Option<PostalDelivery> {
+        let label = format!("{} {}", self.address_number.trim(), self.street_name.trim());
+        Parse::postal_delivery(&label)
+            .ok()
+            .and_then(|(_, delivery)| delivery)
+    }
+
+    /// The `is_physical` method returns `false` if the business address is a non-physical mailing
+    /// address, which cannot match an address with a physical location.
+    pub fn is_physical(&self) -> bool {
+        self.postal_delivery().is_none()
+    }
+
     /// The `community` method returns the postal community name from the `postal_community` field.
     pub fn community(&self) -> String {
         self.postal_community.to_owned()
@@ -469,6 +565,12 @@ impl BusinessLicense {
         self.street_name_post_type
     }
 
+    /// The `post_directional` field represents the street post-directional designation of the
+    /// business address.  This method returns the cloned value of the field.
+    pub fn post_directional(&self) -> Option<StreetNamePreDirectional> {
+        self.street_name_post_directional
+    }
+
     /// The `subaddress_identifier` field represents the subaddress unit identifier associated with
     /// a business address.  This method clones the value of the field.
     pub fn subaddress_identifier(&self) -> Option<String> {
@@ -482,6 +584,10 @@ impl BusinessLicense {
             Some(post_type) => format!("{} {}", self.street_name, post_type),
             None => self.street_name.to_string(),
         };
+        let street_name = match self.street_name_post_directional {
+            Some(post_directional) => format!("{} {}", street_name, post_directional.abbreviate()),
+            None => street_name,
+        };
         let complete_street_name = match self.street_name_pre_directional {
             Some(pre_directional) => format!("{} {}", pre_directional.abbreviate(), street_name),
             None => street_name,
@@ -5,7 +5,8 @@ use clap::Parser;
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     /// The `command` field specifies the command for the program to run. Currently accepts
-    /// 'compare', 'drift', 'filter', 'save', 'orphan_streets' and 'lexisnexis' as values.
+    /// 'compare', 'drift', 'filter', 'geocode', 'nearest', 'save', 'orphan_streets',
+    /// 'lexisnexis', 'mailing', 'nena', 'reconcile' and 'validate' as values.
     ///
     /// * filter
     ///   * takes [`crate::MatchRecords`] as input
@@ -13,26 +14,29 @@ pub struct Cli {
     #[arg(
         short = 'c',
         long,
-        help = "Command to execute.  Valid commands include 'compare', 'drift', 'filter', 'orphan_streets', 'lexisnexis' and 'save'"
+        help = "Command to execute.  Valid commands include 'compare', 'drift', 'filter', 'geocode', 'nearest', 'orphan_streets', 'lexisnexis', 'mailing', 'nena', 'reconcile', 'save' and 'validate'"
     )]
     pub command: String,
     /// The `source` field specifies the path the source address file.
     #[arg(short = 's', long, help = "Path to source addresses.")]
     pub source: std::path::PathBuf,
     /// The `source_type` field contains a designator for the address source.  Currently accepts
-    /// 'grants_pass' and 'josephine_county' as values.
+    /// 'grants_pass', 'josephine_county', 'common' and 'geojson' as values.
     #[arg(short = 'k', long, help = "Address format for source.")]
     pub source_type: Option<String>,
     /// The `target` field specifies the path the target address file.
     #[arg(short = 't', long, help = "Path to target addresses.")]
     pub target: Option<std::path::PathBuf>,
     /// The `target_type` field contains a designator for the address target.  Currently accepts
-    /// 'grants_pass' and 'josephine_county' as values.
+    /// 'grants_pass', 'josephine_county', 'common' and 'geojson' as values.
     #[arg(short = 'z', long, help = "Address format for target.")]
     pub target_type: Option<String>,
     /// The `filter` field contains a value to filter the target data.  Currently accepts
-    /// `missing`, `divergent`, `matching`, `subaddress`, `floor`, `building` and `status` as
-    /// values.
+    /// `missing`, `nonphysical`, `divergent`, `matching` and `probable` as values, and for match
+    /// records the name of any address component, such as `subaddress`, `floor`, `zip` or
+    /// `status`, see [`crate::MatchRecord::mismatch`], keeping the records with a mismatch in that
+    /// component whatever their match status.  For the `validate` command, names a single
+    /// [`crate::ValidationRule`] to check.
     #[arg(short = 'f', long, help = "Filter records by value.")]
     pub filter: Option<String>,
     /// The `duplicates` flag instructs the program to search for duplicate addresses.
@@ -44,7 +48,9 @@ pub struct Cli {
         default_missing_value = "true"
     )]
     pub duplicates: bool,
-    /// The `output` field specifies the path for the output file.
+    /// The `output` field specifies the path for the output file.  Paths with a `geojson`
+    /// extension write a GeoJSON FeatureCollection instead of a CSV file for the `compare`,
+    /// `business`, `drift`, `filter` and `lexisnexis` commands.
     #[arg(
         short = 'o',
         default_value = "output.csv",
@@ -69,4 +75,130 @@ pub struct Cli {
     /// accepts 'grants_pass' and 'josephine_county'.
     #[arg(short = 'y', long, help = "Address format for alternate target.")]
     pub alternate_type: Option<String>,
+    /// The `threshold` field sets the minimum street name similarity, from 0.0 to 1.0, for the
+    /// `compare` command to pair an address with a differently spelled street name.  When absent,
+    /// street names must match exactly.
+    #[arg(
+        long,
+        value_parser = parse_threshold,
+        help = "Similarity threshold for fuzzy street name matching, from 0.0 to 1.0."
+    )]
+    pub threshold: Option<f64>,
+    /// The `drift` field sets the greatest distance allowed between the points of matching
+    /// addresses for the `compare`, `drift` and `reconcile` commands, in meters.  Matches farther
+    /// apart report a drift mismatch.  The distance is measured between longitude and latitude,
+    /// so `projection` and the projected coordinates of the records play no part.  The `drift`
+    /// command defaults to 30 meters.
+    #[arg(
+        long,
+        help = "Maximum distance in meters between matching address points."
+    )]
+    pub drift: Option<f64>,
+    /// The `assign` field directs the `compare` command to pair each source address with at most
+    /// one target address, and each target address with at most one source address.  Target
+    /// addresses left over after pairing write to a second file, named after the output file with
+    /// an `_unassigned` suffix.  Assignment compares street names exactly, so the `compare` command
+    /// rejects `assign` together with `threshold`.
+    #[arg(
+        long,
+        help = "Assign matches one-to-one between source and target.",
+        default_value = "false",
+        default_missing_value = "true"
+    )]
+    pub assign: bool,
+    /// The `column` field names the field in the source CSV holding free-text addresses for the
+    /// `geocode` command.
+    #[arg(
+        long,
+        default_value = "address",
+        help = "Name of the address column to geocode."
+    )]
+    pub column: String,
+    /// The `x_column` field names the field in the source CSV holding X coordinates, or
+    /// longitudes, for the `nearest` command.
+    #[arg(
+        long,
+        default_value = "x",
+        help = "Name of the X coordinate or longitude column."
+    )]
+    pub x_column: String,
+    /// The `y_column` field names the field in the source CSV holding Y coordinates, or
+    /// latitudes, for the `nearest` command.
+    #[arg(
+        long,
+        default_value = "y",
+        help = "Name of the Y coordinate or latitude column."
+    )]
+    pub y_column: String,
+    /// The `projection` field sets the coordinate system of the points read by the `nearest`
+    /// command, e.g. 'wgs84' for GPS longitude and latitude.  See [`crate::Projection`].  Other
+    /// commands ignore it, including the drift distance and one-to-one assignment.
+    #[arg(
+        long,
+        default_value = "web_mercator",
+        value_parser = parse_projection,
+        help = "Coordinate system of the nearest command input points: 'wgs84', 'web_mercator' or 'oregon_south'."
+    )]
+    pub projection: crate::Projection,
+    /// The `neighbors` field sets the number of nearest addresses the `nearest` command returns
+    /// for each point.
+    #[arg(
+        long,
+        default_value = "1",
+        help = "Number of nearest addresses to return per point."
+    )]
+    pub neighbors: usize,
+    /// The `radius` field sets a search distance, in the units of the projected coordinates, for
+    /// the `nearest` command.  When present, the command returns every address within the radius
+    /// of each point instead of the nearest `neighbors` addresses.
+    #[arg(
+        long,
+        help = "Return all addresses within this distance of each point."
+    )]
+    pub radius: Option<f64>,
+    /// The `rules` field specifies the path to a TOML or CSV file of standardization rules,
+    /// replacing the default rules when standardizing county addresses.
+    #[arg(long, help = "Path to standardization rules (TOML or CSV).")]
+    pub rules: Option<std::path::PathBuf>,
+    /// The `temporary_days` field sets the number of days an address may hold a temporary status
+    /// before the `validate` command reports it.
+    #[arg(
+        long,
+        default_value = "365",
+        help = "Days before a temporary address is stale."
+    )]
+    pub temporary_days: i64,
+    /// The `agency` field holds the domain name of the agency minting NGUIDs for the `nena`
+    /// command.
+    #[arg(
+        long,
+        default_value = "grantspassoregon.gov",
+        help = "Agency domain for NGUIDs."
+    )]
+    pub agency: String,
+    /// The `policy` field names the match policy for the `compare` and `reconcile` commands,
+    /// either a predefined policy ('default', 'county' or 'strict') or the path to a TOML policy
+    /// file.  See [`crate::MatchPolicy`].
+    #[arg(long, help = "Match policy name or path to TOML policy file.")]
+    pub policy: Option<String>,
+}
+
+/// The `parse_threshold` function reads a street name similarity threshold from `input`,
+/// rejecting values outside the range 0.0 to 1.0.
+fn parse_threshold(input: &str) -> Result<f64, String> {
+    let threshold = input
+        .parse::<f64>()
+        .map_err(|e| format!("{input} is not a number: {e}"))?;
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err(format!("{threshold} is not between 0.0 and 1.0"))
+    }
+}
+
+/// The `parse_projection` function reads a coordinate system from `input`, see
+/// [`crate::Projection::match_mixed`].
+fn parse_projection(input: &str) -> Result<crate::Projection, String> {
+    crate::Projection::match_mixed(input)
+        .ok_or_else(|| format!("{input} is not a known projection"))
 }
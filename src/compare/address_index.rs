@@ -0,0 +1,143 @@
+//! The `address_index` module contains a lookup table over a set of addresses, used to find
+//! candidate matches without scanning the full comparison set.
+use crate::{Address, AddressNumberRange, StreetNamePreType};
+use std::collections::HashMap;
+
+/// The `AddressIndex` struct holds a reference to a set of comparison addresses, along with a
+/// lookup table keyed by address number and street name.  Built once over the comparison set, the
+/// index returns the subset of addresses that can possibly match a subject address, so that
+/// comparing two sets of addresses does not require checking every pair.
+///
+/// Candidates are returned in the order they appear in the comparison set, so matching against
+/// the candidates produces the same results as scanning the full set.
+#[derive(Debug, Clone)]
+pub struct AddressIndex<'a, T> {
+    /// The `addresses` field holds a reference to the indexed comparison addresses.
+    addresses: &'a [T],
+    /// The `streets` field maps the address number and street name to positions in `addresses`.
+    streets: HashMap<(i64, String), Vec<usize>>,
+    /// The `numbers` field maps the address number to positions in `addresses`, used when the
+    /// street name of the subject address is unknown.
+    numbers: HashMap<i64, Vec<usize>>,
+    /// The `names` field maps the street name to positions in `addresses`, used to find the
+    /// addresses within an address range.
+    names: HashMap<String, Vec<usize>>,
+}
+
+impl<'a, T: Address> AddressIndex<'a, T> {
+    /// The `new` method builds the index over the addresses in `addresses`.
+    #[tracing::instrument(skip_all)]
+    pub fn new(addresses: &'a [T]) -> Self {
+        let mut streets = HashMap::new();
+        let mut numbers = HashMap::new();
+        let mut names = HashMap::new();
+        for (i, address) in addresses.iter().enumerate() {
+            let street = StreetNamePreType::street_key(
+                *address.street_name_pre_type(),
+                address.street_name(),
+            );
+            names.entry(street.clone()).or_insert_with(Vec::new).push(i);
+            streets
+                .entry((address.number(), street))
+                .or_insert_with(Vec::new)
+                .push(i);
+            numbers
+                .entry(address.number())
+                .or_insert_with(Vec::new)
+                .push(i);
+        }
+        tracing::trace!("Indexed {} addresses.", addresses.len());
+        Self {
+            addresses,
+            streets,
+            numbers,
+            names,
+        }
+    }
+
+    /// The `addresses` method returns the indexed comparison addresses.
+    pub fn addresses(&self) -> &'a [T] {
+        self.addresses
+    }
+
+    /// The `candidates` method returns the addresses with address number `number`, street name
+    /// pre type `pre_type` and street name `street_name`, in the order they appear in the
+    /// comparison set.  Streets on numbered highway routes are keyed by route number, see
+    /// [`StreetNamePreType::street_key`].
+    pub fn candidates(
+        &self,
+        number: i64,
+        pre_type: Option<StreetNamePreType>,
+        street_name: &str,
+    ) -> Vec<&'a T> {
+        self.positions(number, pre_type, street_name)
+            .iter()
+            .map(|i| &self.addresses[*i])
+            .collect()
+    }
+
+    /// The `candidates_by_number` method returns the addresses with address number `number`,
+    /// in the order they appear in the comparison set.
+    pub fn candidates_by_number(&self, number: i64) -> Vec<&'a T> {
+        self.positions_by_number(number)
+            .iter()
+            .map(|i| &self.addresses[*i])
+            .collect()
+    }
+
+    /// The `candidates_in_range` method returns the addresses with an address number within
+    /// `range`, as defined by [`AddressNumberRange::contains`], on the street with pre type
+    /// `pre_type` named `street_name`, or on any street if `street_name` is `None`.  Addresses
+    /// return in the order they appear in the comparison set.
+    pub fn candidates_in_range(
+        &self,
+        range: &AddressNumberRange,
+        pre_type: Option<StreetNamePreType>,
+        street_name: Option<&str>,
+    ) -> Vec<&'a T> {
+        let mut positions = match street_name {
+            Some(street_name) => {
+                let street = StreetNamePreType::street_key(pre_type, street_name);
+                self.names
+                    .get(&street)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(|i| range.contains(self.addresses[*i].number()))
+                    .collect::<Vec<usize>>()
+            }
+            None => self
+                .numbers
+                .iter()
+                .filter(|(number, _)| range.contains(**number))
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect(),
+        };
+        positions.sort_unstable();
+        positions.iter().map(|i| &self.addresses[*i]).collect()
+    }
+
+    /// The `positions` method returns the positions in the comparison set of the addresses
+    /// returned by [`AddressIndex::candidates`].
+    pub fn positions(
+        &self,
+        number: i64,
+        pre_type: Option<StreetNamePreType>,
+        street_name: &str,
+    ) -> &[usize] {
+        let street = StreetNamePreType::street_key(pre_type, street_name);
+        match self.streets.get(&(number, street)) {
+            Some(positions) => positions,
+            None => &[],
+        }
+    }
+
+    /// The `positions_by_number` method returns the positions in the comparison set of the
+    /// addresses returned by [`AddressIndex::candidates_by_number`].
+    pub fn positions_by_number(&self, number: i64) -> &[usize] {
+        match self.numbers.get(&number) {
+            Some(positions) => positions,
+            None => &[],
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //!  The `compare_fire` module implements address matching and comparison for Fire Inspections.
 use crate::{
-    from_csv, to_csv, Address, AddressErrorKind, FireInspection, FireInspections, Geographic,
-    IntoCsv, Io, MatchPartialRecord, MatchPartialRecords, MatchStatus,
+    from_csv, to_csv, Address, AddressErrorKind, AddressIndex, FireInspection, FireInspections,
+    Geographic, IntoCsv, Io, MatchPartialRecord, MatchPartialRecords, MatchStatus,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
@@ -32,6 +32,20 @@ impl FireInspectionMatch {
         }
     }
 
+    /// The `indexed` method wraps [`MatchPartialRecord::indexed`], comparing the business address
+    /// from the fire inspection against the candidates returned by `index`.
+    #[tracing::instrument(skip_all)]
+    pub fn indexed<T: Address + Geographic>(
+        inspection: &FireInspection,
+        index: &AddressIndex<T>,
+    ) -> Self {
+        let record = MatchPartialRecord::indexed(inspection.address(), index);
+        FireInspectionMatch {
+            inspection: inspection.clone(),
+            record,
+        }
+    }
+
     /// The `inspection` method returns the cloned value of the `inspection` field, which contains
     /// the fire inspection record.
     pub fn inspection(&self) -> FireInspection {
@@ -52,28 +66,34 @@ pub struct FireInspectionMatches(Vec<FireInspectionMatch>);
 impl FireInspectionMatches {
     /// The `compare` method creates a [`FireInspectionMatch`] for each business address in the
     /// inspection record.  Used to convert [`FireInspections`] into a new instance of
-    /// `FireInspectionMatches`.
+    /// `FireInspectionMatches`.  Builds an [`AddressIndex`] over `addresses` once and matches
+    /// each inspection against the indexed candidates.
     pub fn compare<T: Address + Geographic + Send + Sync>(
         inspections: &FireInspections,
         addresses: &[T],
     ) -> Self {
+        let index = AddressIndex::new(addresses);
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
         )
         .unwrap();
         let records = inspections
             .par_iter()
-            .map(|r| FireInspectionMatch::compare(r, addresses))
+            .map(|r| FireInspectionMatch::indexed(r, &index))
             .progress_with_style(style)
             .collect::<Vec<FireInspectionMatch>>();
         FireInspectionMatches(records)
     }
 
     /// The `filter` method filters records from Self.  Currently accepts values "missing",
-    /// "divergent" and "matching", which filter based on the match status [`MatchStatus`].
+    /// "nonphysical", "divergent" and "matching", which filter based on the match status
+    /// [`MatchStatus`].
     pub fn filter(&mut self, filter: &str) {
         match filter {
             "missing" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Missing),
+            "nonphysical" => {
+                self.retain(|r| r.record()[0].match_status() == MatchStatus::NonPhysical)
+            }
             "divergent" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Divergent),
             "matching" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Matching),
             _ => info!("Invalid filter provided."),
@@ -119,11 +139,12 @@ impl FireInspectionMatchRecords {
     //    Ok(Self(records))
     //}
     /// The `filter` method returns the subset of records that match the filter.  Current values
-    /// for the `filter` field include "missing", "divergent", "matching", which filter by address
-    /// match status.
+    /// for the `filter` field include "missing", "nonphysical", "divergent", "matching", which
+    /// filter by address match status.
     pub fn filter(&mut self, filter: &str) {
         match filter {
             "missing" => self.retain(|r| r.status() == MatchStatus::Missing),
+            "nonphysical" => self.retain(|r| r.status() == MatchStatus::NonPhysical),
             "divergent" => self.retain(|r| r.status() == MatchStatus::Divergent),
             "matching" => self.retain(|r| r.status() == MatchStatus::Matching),
             _ => info!("Invalid filter provided."),
@@ -1,8 +1,11 @@
 //! The `eponym` module is the eponymous module for `compare`.  Contains types and methods for
 //! comparing addresses.
 use crate::{
-    from_csv, to_csv, Address, AddressErrorKind, AddressStatus, Geographic, IntoCsv, Io,
-    PartialAddress, PartialAddresses, SubaddressType,
+    from_csv, street_name_similarity, to_csv, to_geojson, Address, AddressErrorKind, AddressIndex,
+    AddressStatus, CommonAddress, ComponentPolicy, Geographic, IntoCsv, IntoGeoJson, Io,
+    MatchPolicy, NguidGenerator, PartialAddress, PartialAddresses, State, StreetNamePostType,
+    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
+    SubaddressType, Validate,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
@@ -11,20 +14,106 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 /// The `Mismatch` enum tracks the fields of an address that can diverge while still potentially
-/// referring to the same location.
+/// referring to the same location.  Which fields can diverge depends on the [`MatchPolicy`] of the
+/// comparison.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum Mismatch {
+    /// Represents a mismatch in the address number.
+    Number(String),
+    /// Represents a mismatch in the address number suffix.
+    NumberSuffix(String),
+    /// Represents a mismatch in the street name pre directional.
+    Directional(String),
+    /// Represents a mismatch in the street name pre modifier.
+    PreModifier(String),
+    /// Represents a mismatch in the street name, including the street name pre type.
+    StreetName(String),
+    /// Represents a mismatch in the separator element.
+    Separator(String),
+    /// Represents a mismatch in the street name post type.
+    StreetType(String),
+    /// Represents a mismatch in the street name post directional.
+    PostDirectional(String),
     /// Represents a mismatch in the subaddress type.
     SubaddressType(String),
+    /// Represents a mismatch in the subaddress identifier.
+    SubaddressId(String),
     /// Represents a mismatch in the floor number.
     Floor(String),
     /// Represents a mismatch in the building identifier.
     Building(String),
+    /// Represents a mismatch in the zip code.
+    Zip(String),
+    /// Represents a mismatch in the postal community.
+    PostalCommunity(String),
+    /// Represents a mismatch in the state.
+    State(String),
     /// Represents a mismatch in the address status.
     Status(String),
+    /// Represents a distance between the address points greater than the maximum set by the
+    /// match policy.
+    Drift(String),
 }
 
 impl Mismatch {
+    /// The `number` method captures information about the mismatch between the address numbers as
+    /// a message contained in the enum variant.
+    pub fn number(from: i64, to: i64) -> Self {
+        Self::Number(format!("{} not equal to {}", from, to))
+    }
+
+    /// The `number_suffix` method captures information about the mismatch between the address
+    /// number suffixes as a message contained in the enum variant.
+    pub fn number_suffix(from: Option<String>, to: Option<String>) -> Self {
+        Self::NumberSuffix(format!("{:?} not equal to {:?}", from, to))
+    }
+
+    /// The `directional` method captures information about the mismatch between the street name
+    /// pre directionals as a message contained in the enum variant.
+    pub fn directional(
+        from: Option<StreetNamePreDirectional>,
+        to: Option<StreetNamePreDirectional>,
+    ) -> Self {
+        Self::Directional(format!("{:?} not equal to {:?}", from, to))
+    }
+
+    /// The `pre_modifier` method captures information about the mismatch between the street name
+    /// pre modifiers as a message contained in the enum variant.
+    pub fn pre_modifier(
+        from: Option<StreetNamePreModifier>,
+        to: Option<StreetNamePreModifier>,
+    ) -> Self {
+        Self::PreModifier(format!("{:?} not equal to {:?}", from, to))
+    }
+
+    /// The `street_name` method captures information about the mismatch between the street names
+    /// as a message contained in the enum variant.  The street names include any street name pre
+    /// type, as in [`crate::Address::common_street_name`].
+    pub fn street_name(from: String, to: String) -> Self {
+        Self::StreetName(format!("{} not equal to {}", from, to))
+    }
+
+    /// The `separator` method captures information about the mismatch between the separator
+    /// elements as a message contained in the enum variant.
+    pub fn separator(from: Option<StreetSeparator>, to: Option<StreetSeparator>) -> Self {
+        Self::Separator(format!("{:?} not equal to {:?}", from, to))
+    }
+
+    /// The `street_type` method captures information about the mismatch between the street name
+    /// post types as a message contained in the enum variant.
+    pub fn street_type(from: Option<StreetNamePostType>, to: Option<StreetNamePostType>) -> Self {
+        Self::StreetType(format!("{:?} not equal to {:?}", from, to))
+    }
+
+    /// The `post_directional` method captures information about the mismatch between the street
+    /// name post directionals as a message contained in the enum variant.
+    pub fn post_directional(
+        from: Option<StreetNamePreDirectional>,
+        to: Option<StreetNamePreDirectional>,
+    ) -> Self {
+        Self::PostDirectional(format!("{:?} not equal to {:?}", from, to))
+    }
+
     /// The `subaddress_type` method captures information about the mismatch between subaddress
     /// type fields as a message contained in the enum variant.
     pub fn subaddress_type(from: Option<SubaddressType>, to: Option<SubaddressType>) -> Self {
@@ -32,6 +121,12 @@ impl Mismatch {
         Self::SubaddressType(message)
     }
 
+    /// The `subaddress_id` method captures information about the mismatch between the subaddress
+    /// identifiers as a message contained in the enum variant.
+    pub fn subaddress_id(from: Option<String>, to: Option<String>) -> Self {
+        Self::SubaddressId(format!("{:?} not equal to {:?}", from, to))
+    }
+
     /// The `floor` method captures information about the mismatch between the `floor` fields as a message contained in the enum variant.
     pub fn floor(from: Option<i64>, to: Option<i64>) -> Self {
         let message = format!("{:?} not equal to {:?}", from, to);
@@ -44,11 +139,35 @@ impl Mismatch {
         Self::Building(message)
     }
 
+    /// The `zip` method captures information about the mismatch between the zip codes as a message
+    /// contained in the enum variant.
+    pub fn zip(from: i64, to: i64) -> Self {
+        Self::Zip(format!("{} not equal to {}", from, to))
+    }
+
+    /// The `postal_community` method captures information about the mismatch between the postal
+    /// communities as a message contained in the enum variant.
+    pub fn postal_community(from: String, to: String) -> Self {
+        Self::PostalCommunity(format!("{} not equal to {}", from, to))
+    }
+
+    /// The `state` method captures information about the mismatch between the states as a message
+    /// contained in the enum variant.
+    pub fn state(from: State, to: State) -> Self {
+        Self::State(format!("{} not equal to {}", from, to))
+    }
+
     /// The `status` method captures information about the mismatch between the `status` fields as a message contained in the enum variant.
     pub fn status(from: AddressStatus, to: AddressStatus) -> Self {
         let message = format!("{} not equal to {}", from, to);
         Self::Status(message)
     }
+
+    /// The `drift` method captures information about the distance between the address points as a
+    /// message contained in the enum variant.
+    pub fn drift(distance: f64, max: f64) -> Self {
+        Self::Drift(format!("{distance:.1} greater than {max}"))
+    }
 }
 
 /// The `Mismatches` struct holds a vector of type [`Mismatch`].
@@ -103,8 +222,11 @@ impl AddressMatch {
 }
 
 /// The `MatchStatus` enum delineates whether a given address has a match (the `Matching` variant),
-/// has a match but differs in some descriptive fields (the `Divergent` variant), or does not have
-/// a match in the comparison set (the `Missing` variant).
+/// has a match but differs in some descriptive fields (the `Divergent` variant), has a likely
+/// match under a different spelling of the street name (the `Probable` variant), or does not have
+/// a match in the comparison set (the `Missing` variant).  Mailing addresses without a physical
+/// location, such as PO boxes, cannot match and take the `NonPhysical` variant instead.  A
+/// one-to-one assignment marks matches left over after pairing with the `Unassigned` variant.
 ///
 /// We have derived Default using the Missing variant, mostly so structs that take a `MatchStatus`
 /// as a field can also derive default.  Properly speaking, there is no meaningful default for this
@@ -117,6 +239,18 @@ pub enum MatchStatus {
     /// the address contains fields with different values than in the comparison (e.g. the
     /// address has status 'Retired' compared to 'Current').
     Divergent,
+    /// The `Probable` variant indicates an address has no exact match in the comparison set, but
+    /// a comparison address differs only in the spelling of the street name, with a similarity
+    /// score at or above the threshold set by the caller (e.g. "MC LEAN" compared to "MCLEAN").
+    Probable,
+    /// The `NonPhysical` variant indicates the address is a mailing address without a physical
+    /// location, such as a PO box, rural route or general delivery, and cannot have a match in the
+    /// comparison set.
+    NonPhysical,
+    /// The `Unassigned` variant indicates the address has a match in the comparison set, but a
+    /// one-to-one assignment paired each matching address with another address instead.  See
+    /// [`MatchRecords::assign`].
+    Unassigned,
     #[default]
     /// The `Missing` variant indicates the address does not have a match in the comparison set.
     Missing,
@@ -124,6 +258,9 @@ pub enum MatchStatus {
 
 /// A `MatchRecord` reports the match results for a single address compared against a set of
 /// addresses.  Designed to plot and diagnose missing and divergent addresses.
+///
+/// Fields after `id` were added later, and default to empty when missing, so that match records
+/// written by earlier versions still read.  Add new fields at the end to keep the column order.
 #[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct MatchRecord {
     /// The `match_status` field represents the match status of the address.
@@ -147,8 +284,233 @@ pub struct MatchRecord {
     /// The `latitude` field represents the 'y' value of the address point.  Depending on the
     /// input from the caller, the value may be in decimal degrees, meters or feet.
     pub latitude: f64,
-    /// The `id` field is an internal unique id.
+    /// The `id` field is an id for the subject address, shared by each record of the subject.  The
+    /// id is the Global ID of the subject address from [`NguidGenerator::record_id`], or for
+    /// records without a Global ID, derives from the label using [`NguidGenerator::stable_id`], so
+    /// it stays the same between runs.
     pub id: uuid::Uuid,
+    /// The `number` field indicates the subject address and its match, if present, have different
+    /// address numbers.
+    #[serde(default)]
+    pub number: Option<String>,
+    /// The `number_suffix` field indicates the subject address and its match, if present, have
+    /// different address number suffixes.
+    #[serde(default)]
+    pub number_suffix: Option<String>,
+    /// The `directional` field indicates the subject address and its match, if present, have
+    /// different street name pre directionals.
+    #[serde(default)]
+    pub directional: Option<String>,
+    /// The `pre_modifier` field indicates the subject address and its match, if present, have
+    /// different street name pre modifiers.
+    #[serde(default)]
+    pub pre_modifier: Option<String>,
+    /// The `street_name` field indicates the subject address and its match, if present, have
+    /// different street names.
+    #[serde(default)]
+    pub street_name: Option<String>,
+    /// The `separator` field indicates the subject address and its match, if present, have
+    /// different separator elements.
+    #[serde(default)]
+    pub separator: Option<String>,
+    /// The `street_type` field indicates the subject address and its match, if present, have
+    /// different street name post types.  E.g. "STREET" does not match "AVENUE".
+    #[serde(default)]
+    pub street_type: Option<String>,
+    /// The `post_directional` field indicates the subject address and its match, if present, have
+    /// different street name post directionals.
+    #[serde(default)]
+    pub post_directional: Option<String>,
+    /// The `subaddress_id` field indicates the subject address and its match, if present, have
+    /// different subaddress identifiers.
+    #[serde(default)]
+    pub subaddress_id: Option<String>,
+    /// The `zip` field indicates the subject address and its match, if present, have different
+    /// zip codes.
+    #[serde(default)]
+    pub zip: Option<String>,
+    /// The `postal_community` field indicates the subject address and its match, if present, have
+    /// different postal communities.
+    #[serde(default)]
+    pub postal_community: Option<String>,
+    /// The `state` field indicates the subject address and its match, if present, have different
+    /// states.
+    #[serde(default)]
+    pub state: Option<String>,
+    /// The `drift` field indicates the points of the subject address and its match, if present,
+    /// are farther apart than the maximum distance set by the match policy.
+    #[serde(default)]
+    pub drift: Option<String>,
+    /// The `other_label` field is the text representation of the comparison address paired with
+    /// the subject address, if present.  For a fuzzy comparison, this is the best candidate.
+    #[serde(default)]
+    pub other_label: Option<String>,
+    /// The `similarity` field holds the street name similarity score between the subject address
+    /// and the comparison address in `other_label`, from 0.0 to 1.0.  Only set by a fuzzy
+    /// comparison.
+    #[serde(default)]
+    pub similarity: Option<f64>,
+    /// The `distance` field holds the distance between the subject address and the comparison
+    /// address in `other_label`, in meters by [`Geographic::haversine`].  Only set when the match
+    /// policy does not ignore drift.
+    #[serde(default)]
+    pub distance: Option<f64>,
+}
+
+impl MatchRecord {
+    /// The `missing` method creates a record with match status [`MatchStatus::Missing`] for the
+    /// subject address.
+    fn missing<T: Address + Geographic>(self_address: &T, id: uuid::Uuid) -> Self {
+        MatchRecord {
+            match_status: MatchStatus::Missing,
+            address_label: self_address.label(),
+            longitude: self_address.longitude(),
+            latitude: self_address.latitude(),
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// The `unassigned` method creates a record with match status [`MatchStatus::Unassigned`] for
+    /// the address `address`.
+    fn unassigned<T: Validate + Geographic>(address: &T) -> Self {
+        MatchRecord {
+            match_status: MatchStatus::Unassigned,
+            ..Self::missing(address, NguidGenerator::record_id(address))
+        }
+    }
+
+    /// The `paired` method creates a record pairing the subject address with the comparison
+    /// address `other`, recording any mismatches from `address_match` in the respective fields.
+    fn paired<T: Address + Geographic, U: Address>(
+        self_address: &T,
+        other: &U,
+        match_status: MatchStatus,
+        address_match: &AddressMatch,
+        distance: Option<f64>,
+        id: uuid::Uuid,
+    ) -> Self {
+        let mut record = MatchRecord {
+            match_status,
+            other_label: Some(other.label()),
+            distance,
+            ..Self::missing(self_address, id)
+        };
+        if let Some(mismatches) = &address_match.mismatches {
+            for mismatch in mismatches.iter() {
+                let (field, message) = match mismatch {
+                    Mismatch::Number(message) => (&mut record.number, message),
+                    Mismatch::NumberSuffix(message) => (&mut record.number_suffix, message),
+                    Mismatch::Directional(message) => (&mut record.directional, message),
+                    Mismatch::PreModifier(message) => (&mut record.pre_modifier, message),
+                    Mismatch::StreetName(message) => (&mut record.street_name, message),
+                    Mismatch::Separator(message) => (&mut record.separator, message),
+                    Mismatch::StreetType(message) => (&mut record.street_type, message),
+                    Mismatch::PostDirectional(message) => (&mut record.post_directional, message),
+                    Mismatch::SubaddressType(message) => (&mut record.subaddress_type, message),
+                    Mismatch::SubaddressId(message) => (&mut record.subaddress_id, message),
+                    Mismatch::Floor(message) => (&mut record.floor, message),
+                    Mismatch::Building(message) => (&mut record.building, message),
+                    Mismatch::Zip(message) => (&mut record.zip, message),
+                    Mismatch::PostalCommunity(message) => (&mut record.postal_community, message),
+                    Mismatch::State(message) => (&mut record.state, message),
+                    Mismatch::Status(message) => (&mut record.status, message),
+                    Mismatch::Drift(message) => (&mut record.drift, message),
+                };
+                *field = Some(message.to_owned());
+            }
+        }
+        record
+    }
+
+    /// The `COMPONENTS` constant holds the names of the address components with a mismatch field
+    /// in the record, as accepted by [`MatchRecord::mismatch`].
+    pub const COMPONENTS: [&'static str; 17] = [
+        "number",
+        "number_suffix",
+        "directional",
+        "pre_modifier",
+        "street_name",
+        "separator",
+        "street_type",
+        "post_directional",
+        "subaddress_type",
+        "subaddress_id",
+        "floor",
+        "building",
+        "zip",
+        "postal_community",
+        "state",
+        "status",
+        "drift",
+    ];
+
+    /// The `mismatches` method returns the name and message of each mismatch in the record, in
+    /// the order of [`MatchRecord::COMPONENTS`].
+    pub fn mismatches(&self) -> Vec<(&'static str, &String)> {
+        Self::COMPONENTS
+            .iter()
+            .filter_map(|component| match self.mismatch(component) {
+                Some(Some(message)) => Some((*component, message)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The `component` method returns the name in [`MatchRecord::COMPONENTS`] of the address
+    /// component named `name`, or `None` if `name` does not name a component.  Component names
+    /// are the field names of [`MatchPolicy`], e.g. "zip" or "street_type", and also read with
+    /// spaces or hyphens in place of underscores, or by common aliases such as "subaddress".
+    pub fn component(name: &str) -> Option<&'static str> {
+        let component = match name.to_lowercase().replace([' ', '-'], "_").as_str() {
+            "number" | "address_number" => "number",
+            "number_suffix" | "address_number_suffix" => "number_suffix",
+            "directional" | "pre_directional" => "directional",
+            "pre_modifier" => "pre_modifier",
+            "street_name" | "pre_type" => "street_name",
+            "separator" => "separator",
+            "street_type" | "post_type" => "street_type",
+            "post_directional" => "post_directional",
+            "subaddress" | "subaddress_type" => "subaddress_type",
+            "subaddress_id" | "subaddress_identifier" => "subaddress_id",
+            "floor" => "floor",
+            "building" => "building",
+            "zip" | "zip_code" => "zip",
+            "postal_community" => "postal_community",
+            "state" => "state",
+            "status" => "status",
+            "drift" | "distance" => "drift",
+            _ => return None,
+        };
+        Some(component)
+    }
+
+    /// The `mismatch` method returns the mismatch field of the record for the address component
+    /// named `component`, as read by [`MatchRecord::component`], or `None` if `component` does not
+    /// name a component.
+    pub fn mismatch(&self, component: &str) -> Option<&Option<String>> {
+        let field = match Self::component(component)? {
+            "number" => &self.number,
+            "number_suffix" => &self.number_suffix,
+            "directional" => &self.directional,
+            "pre_modifier" => &self.pre_modifier,
+            "street_name" => &self.street_name,
+            "separator" => &self.separator,
+            "street_type" => &self.street_type,
+            "post_directional" => &self.post_directional,
+            "subaddress_type" => &self.subaddress_type,
+            "subaddress_id" => &self.subaddress_id,
+            "floor" => &self.floor,
+            "building" => &self.building,
+            "zip" => &self.zip,
+            "postal_community" => &self.postal_community,
+            "state" => &self.state,
+            "status" => &self.status,
+            "drift" => &self.drift,
+            _ => return None,
+        };
+        Some(field)
+    }
 }
 
 impl Geographic for MatchRecord {
@@ -170,93 +532,209 @@ impl MatchRecords {
     /// addresses, and returns the `MatchRecords` for the subject address.  A subject address can
     /// match against multiple candidates (e.g. a parent address will match against all
     /// subaddresses associated with the parent), so the result type must potentially accommodate
-    /// multiple records.
-    pub fn new<T: Address + Geographic, U: Address + Geographic>(
+    /// multiple records.  Compares addresses using the match policy `policy`.
+    pub fn new<T: Validate + Geographic, U: Validate + Geographic>(
         self_address: &T,
         other_addresses: &[U],
+        policy: &MatchPolicy,
+    ) -> Self {
+        Self::from_candidates(self_address, other_addresses, policy)
+    }
+
+    /// The `indexed` method produces the same `MatchRecords` as [`MatchRecords::new`], but only
+    /// compares the subject address against the candidates returned by `index`, instead of
+    /// scanning the full set of comparison addresses.  When the match policy `policy` does not
+    /// require the street name to agree, the candidates include every address with the same
+    /// address number, and when it does not require the address number to agree, the candidates
+    /// include every indexed address.
+    pub fn indexed<T: Validate + Geographic, U: Validate + Geographic>(
+        self_address: &T,
+        index: &AddressIndex<U>,
+        policy: &MatchPolicy,
     ) -> Self {
-        let address_label = self_address.label();
-        let latitude = self_address.latitude();
-        let longitude = self_address.longitude();
-        let id = uuid::Uuid::new_v4();
+        let addresses = index.addresses();
+        let candidates = Self::candidate_positions(self_address, index, policy)
+            .into_iter()
+            .map(|i| &addresses[i]);
+        Self::from_candidates(self_address, candidates, policy)
+    }
 
+    /// The `candidate_positions` method returns the positions in the comparison set of the
+    /// addresses in `index` that can match `self_address` under the match policy `policy`.
+    fn candidate_positions<T: Address, U: Address>(
+        self_address: &T,
+        index: &AddressIndex<U>,
+        policy: &MatchPolicy,
+    ) -> Vec<usize> {
+        match (policy.number, policy.street_name) {
+            (ComponentPolicy::Required, ComponentPolicy::Required) => index
+                .positions(
+                    self_address.number(),
+                    *self_address.street_name_pre_type(),
+                    self_address.street_name(),
+                )
+                .to_vec(),
+            (ComponentPolicy::Required, _) => {
+                index.positions_by_number(self_address.number()).to_vec()
+            }
+            _ => (0..index.addresses().len()).collect(),
+        }
+    }
+
+    /// The `drift` method applies the drift policy of `policy` to `address_match`, the result of
+    /// comparing `self_address` against `other`.  Returns the result along with the distance
+    /// between the addresses in meters by [`Geographic::haversine`], if measured, whatever the
+    /// projected coordinates of the addresses.  A distance greater than the maximum set by `policy`
+    /// adds a [`Mismatch::Drift`], or if drift is required, makes the addresses not coincident.
+    fn drift<T: Geographic + ?Sized, U: Geographic + ?Sized>(
+        self_address: &T,
+        other: &U,
+        policy: &MatchPolicy,
+        mut address_match: AddressMatch,
+    ) -> (AddressMatch, Option<f64>) {
+        if policy.drift == ComponentPolicy::Ignored || !address_match.coincident {
+            return (address_match, None);
+        }
+        let distance = self_address.haversine(other);
+        if let Some(max) = policy.max_drift.filter(|max| distance > *max) {
+            match policy.drift {
+                ComponentPolicy::Required => return (AddressMatch::new(false, Vec::new()), None),
+                _ => address_match
+                    .mismatches
+                    .get_or_insert_with(Mismatches::default)
+                    .push(Mismatch::drift(distance, max)),
+            }
+        }
+        (address_match, Some(distance))
+    }
+
+    /// The `from_candidates` method compares the subject address against each address in
+    /// `candidates`, producing a match record for each coincident address, or a missing record if
+    /// no coincident address is found.
+    fn from_candidates<'a, T: Validate + Geographic, U: Validate + Geographic + 'a>(
+        self_address: &T,
+        candidates: impl IntoIterator<Item = &'a U>,
+        policy: &MatchPolicy,
+    ) -> Self {
+        let id = NguidGenerator::record_id(self_address);
         let mut match_record = Vec::new();
+        for address in candidates {
+            let (address_match, distance) = Self::drift(
+                self_address,
+                address,
+                policy,
+                self_address.coincident_with(address, policy),
+            );
+            if address_match.coincident {
+                let match_status = match address_match.mismatches {
+                    None => MatchStatus::Matching,
+                    Some(_) => MatchStatus::Divergent,
+                };
+                match_record.push(MatchRecord::paired(
+                    self_address,
+                    address,
+                    match_status,
+                    &address_match,
+                    distance,
+                    id,
+                ));
+            }
+        }
+        if match_record.is_empty() {
+            match_record.push(MatchRecord::missing(self_address, id))
+        }
+        MatchRecords(match_record)
+    }
 
-        for address in other_addresses {
-            let address_match = self_address.coincident(address);
+    /// The `fuzzy` method compares the subject address against the addresses in `index`, first
+    /// looking for exact matches as in [`MatchRecords::indexed`].  If none are found, the method
+    /// scores the street name of each candidate with the same address number using
+    /// [`crate::street_name_similarity`], keeping the highest scoring candidate that is otherwise
+    /// coincident with the subject address.  If the score is at or above `threshold`, the record
+    /// has status [`MatchStatus::Probable`], otherwise it has status [`MatchStatus::Missing`].  In
+    /// either case, the record reports the best candidate and its score, if any.  Compares
+    /// addresses using the match policy `policy`.
+    pub fn fuzzy<T: Validate + Geographic, U: Validate + Geographic>(
+        self_address: &T,
+        index: &AddressIndex<U>,
+        threshold: f64,
+        policy: &MatchPolicy,
+    ) -> Self {
+        let mut records = Self::indexed(self_address, index, policy);
+        if records
+            .iter()
+            .any(|r| r.match_status != MatchStatus::Missing)
+        {
+            records.iter_mut().for_each(|r| r.similarity = Some(1.0));
+            return records;
+        }
+        let id = records[0].id;
+        // Substitute the street name of each candidate into the subject address, so that
+        // coincidence checks every other component as usual.
+        let mut subject = CommonAddress::from(self_address);
+        let mut best: Option<(f64, &U, AddressMatch, Option<f64>)> = None;
+        for candidate in index.candidates_by_number(self_address.number()) {
+            let score = street_name_similarity(self_address.street_name(), candidate.street_name());
+            *subject.street_name_mut() = candidate.street_name().clone();
+            let (address_match, distance) = Self::drift(
+                self_address,
+                candidate,
+                policy,
+                subject.coincident_with(candidate, policy),
+            );
             if address_match.coincident {
-                let mut subaddress_type = None;
-                let mut floor = None;
-                let mut building = None;
-                let mut status = None;
-                match address_match.mismatches {
-                    None => match_record.push(MatchRecord {
-                        match_status: MatchStatus::Matching,
-                        address_label: address_label.clone(),
-                        subaddress_type,
-                        floor,
-                        building,
-                        status,
-                        longitude,
-                        latitude,
-                        id,
-                    }),
-                    Some(mismatches) => {
-                        for mismatch in mismatches.iter() {
-                            match mismatch {
-                                Mismatch::SubaddressType(message) => {
-                                    subaddress_type = Some(message.to_owned())
-                                }
-                                Mismatch::Floor(message) => floor = Some(message.to_owned()),
-                                Mismatch::Building(message) => building = Some(message.to_owned()),
-                                Mismatch::Status(message) => status = Some(message.to_owned()),
-                            }
-                        }
-                        match_record.push(MatchRecord {
-                            match_status: MatchStatus::Divergent,
-                            address_label: address_label.clone(),
-                            subaddress_type,
-                            floor,
-                            building,
-                            status,
-                            longitude,
-                            latitude,
-                            id,
-                        })
-                    }
+                match &best {
+                    Some((best_score, _, _, _)) if *best_score >= score => {}
+                    _ => best = Some((score, candidate, address_match, distance)),
                 }
             }
         }
-        if match_record.is_empty() {
-            match_record.push(MatchRecord {
-                match_status: MatchStatus::Missing,
-                address_label,
-                subaddress_type: None,
-                floor: None,
-                building: None,
-                status: None,
-                longitude,
-                latitude,
+        if let Some((score, candidate, address_match, distance)) = best {
+            tracing::trace!(
+                "Best candidate for {} is {} with score {score}",
+                self_address.label(),
+                candidate.label()
+            );
+            let match_status = if score >= threshold {
+                MatchStatus::Probable
+            } else {
+                MatchStatus::Missing
+            };
+            let mut record = MatchRecord::paired(
+                self_address,
+                candidate,
+                match_status,
+                &address_match,
+                distance,
                 id,
-            })
+            );
+            record.similarity = Some(score);
+            records = MatchRecords(vec![record]);
         }
-        MatchRecords(match_record)
+        records
     }
 
     /// For each address in `self_addresses`, the `compare` method calculates the match record for
     /// the subject address compared against the addresses in `other_addresses`, and returns the
-    /// results in a [`MatchRecords`] struct.
-    pub fn compare<T: Address + Geographic + Send + Sync, U: Address + Geographic + Send + Sync>(
+    /// results in a [`MatchRecords`] struct.  Builds an [`AddressIndex`] over `other_addresses`
+    /// once, so each subject address is only compared against candidates with the same address
+    /// number and street name, as required by the match policy `policy`.
+    pub fn compare<
+        T: Validate + Geographic + Send + Sync,
+        U: Validate + Geographic + Send + Sync,
+    >(
         self_addresses: &[T],
         other_addresses: &[U],
+        policy: &MatchPolicy,
     ) -> Self {
+        let index = AddressIndex::new(other_addresses);
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
         )
         .unwrap();
         let record = self_addresses
             .par_iter()
-            .map(|address| MatchRecords::new(address, other_addresses))
+            .map(|address| MatchRecords::indexed(address, &index, policy))
             .progress_with_style(style)
             .collect::<Vec<MatchRecords>>();
         let mut records = Vec::new();
@@ -266,30 +744,170 @@ impl MatchRecords {
         MatchRecords(records)
     }
 
+    /// The `compare_fuzzy` method works like [`MatchRecords::compare`], but calls
+    /// [`MatchRecords::fuzzy`] on each subject address, so that addresses with a near-miss
+    /// spelling of the street name in `other_addresses` report the best candidate, and have
+    /// status [`MatchStatus::Probable`] if the similarity score is at or above `threshold`.
+    /// Compares addresses using the match policy `policy`.
+    pub fn compare_fuzzy<
+        T: Validate + Geographic + Send + Sync,
+        U: Validate + Geographic + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        threshold: f64,
+        policy: &MatchPolicy,
+    ) -> Self {
+        let index = AddressIndex::new(other_addresses);
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let record = self_addresses
+            .par_iter()
+            .map(|address| MatchRecords::fuzzy(address, &index, threshold, policy))
+            .progress_with_style(style)
+            .collect::<Vec<MatchRecords>>();
+        let mut records = Vec::new();
+        for mut item in record {
+            records.append(&mut item);
+        }
+        MatchRecords(records)
+    }
+
+    /// The `assign` method compares `self_addresses` against `other_addresses` like
+    /// [`MatchRecords::compare`], but pairs each subject address with at most one comparison
+    /// address, and each comparison address with at most one subject address.  Exact matches take
+    /// priority over divergent matches, and among matches of the same status, the nearest pair
+    /// by [`Geographic::haversine`] takes priority.  Subject addresses whose matches all pair with
+    /// other subjects have status [`MatchStatus::Unassigned`], as do the records of the leftover
+    /// comparison addresses in [`MatchAssignment::unassigned`].  Compares addresses using the
+    /// match policy `policy`.
+    pub fn assign<
+        T: Validate + Geographic + Send + Sync,
+        U: Validate + Geographic + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+    ) -> MatchAssignment {
+        let index = AddressIndex::new(other_addresses);
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let mut pairs = self_addresses
+            .par_iter()
+            .enumerate()
+            .progress_with_style(style)
+            .flat_map_iter(|(i, address)| {
+                Self::candidate_positions(address, &index, policy)
+                    .into_iter()
+                    .filter_map(move |j| {
+                        let other = &other_addresses[j];
+                        let (address_match, distance) = Self::drift(
+                            address,
+                            other,
+                            policy,
+                            address.coincident_with(other, policy),
+                        );
+                        if !address_match.coincident {
+                            return None;
+                        }
+                        let match_status = match address_match.mismatches {
+                            None => MatchStatus::Matching,
+                            Some(_) => MatchStatus::Divergent,
+                        };
+                        Some((
+                            match_status,
+                            address.haversine(other),
+                            i,
+                            j,
+                            address_match,
+                            distance,
+                        ))
+                    })
+            })
+            .collect::<Vec<(MatchStatus, f64, usize, usize, AddressMatch, Option<f64>)>>();
+        pairs.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+        });
+        // Pair greedily in order of priority, noting which addresses had a match at all.
+        let mut assigned: Vec<Option<MatchRecord>> = vec![None; self_addresses.len()];
+        let mut self_matched = vec![false; self_addresses.len()];
+        let mut other_matched = vec![false; other_addresses.len()];
+        let mut other_assigned = vec![false; other_addresses.len()];
+        for (match_status, _, i, j, address_match, distance) in pairs {
+            self_matched[i] = true;
+            other_matched[j] = true;
+            if assigned[i].is_none() && !other_assigned[j] {
+                let address = &self_addresses[i];
+                let id = NguidGenerator::record_id(address);
+                assigned[i] = Some(MatchRecord::paired(
+                    address,
+                    &other_addresses[j],
+                    match_status,
+                    &address_match,
+                    distance,
+                    id,
+                ));
+                other_assigned[j] = true;
+            }
+        }
+        let records = assigned
+            .into_iter()
+            .zip(self_addresses)
+            .zip(self_matched)
+            .map(|((record, address), matched)| match record {
+                Some(record) => record,
+                None if matched => MatchRecord::unassigned(address),
+                None => MatchRecord::missing(address, NguidGenerator::record_id(address)),
+            })
+            .collect::<Vec<MatchRecord>>();
+        let unassigned = other_addresses
+            .iter()
+            .zip(other_matched.iter().zip(&other_assigned))
+            .filter(|(_, (matched, assigned))| **matched && !**assigned)
+            .map(|(address, _)| MatchRecord::unassigned(address))
+            .collect::<Vec<MatchRecord>>();
+        tracing::info!("{} comparison addresses left unassigned.", unassigned.len());
+        let unpaired = other_assigned
+            .iter()
+            .enumerate()
+            .filter(|(_, assigned)| !**assigned)
+            .map(|(j, _)| j)
+            .collect::<Vec<usize>>();
+        MatchAssignment {
+            records: MatchRecords(records),
+            unassigned: MatchRecords(unassigned),
+            unpaired,
+        }
+    }
+
     /// The `filter` method returns the subset of `MatchRecords` that meet the filter requirement.
     /// The `filter` parameter takes a string reference that can take the values "matching",
-    /// "missing", "divergent", "subaddress", "floor", "building" and "status".  When filtering by
-    /// match status, the return records contain those records where the match status equals the
-    /// filter value.  For the mismatch fields, the return records contain values where a mismatch
-    /// is present in the provided field.
+    /// "missing", "divergent", "probable" and "unassigned", or the name of an address component
+    /// read by [`MatchRecord::component`], such as "subaddress", "floor", "zip" or "status".  When
+    /// filtering by match status, the return records contain those records where the match status
+    /// equals the filter value.  For the mismatch fields, the return records contain values where
+    /// a mismatch is present in the provided field, whatever the match status, so divergent and
+    /// probable records both qualify.
     pub fn filter(mut self, filter: &str) -> Self {
         match filter {
             "matching" => self.retain(|r| r.match_status == MatchStatus::Matching),
             "missing" => self.retain(|r| r.match_status == MatchStatus::Missing),
             "divergent" => self.retain(|r| r.match_status == MatchStatus::Divergent),
-            "subaddress" => self.retain(|r| {
-                r.match_status == MatchStatus::Divergent && r.subaddress_type.is_some()
-            }),
-            "floor" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.floor.is_some())
-            }
-            "building" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.building.is_some())
-            }
-            "status" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.status.is_some())
-            }
-            _ => info!("Invalid filter provided."),
+            "probable" => self.retain(|r| r.match_status == MatchStatus::Probable),
+            "unassigned" => self.retain(|r| r.match_status == MatchStatus::Unassigned),
+            _ => match MatchRecord::component(filter) {
+                Some(component) => {
+                    self.retain(|r| r.mismatch(component).is_some_and(Option::is_some))
+                }
+                None => info!("Invalid filter provided."),
+            },
         }
         self
     }
@@ -306,6 +924,26 @@ impl IntoCsv<MatchRecords> for MatchRecords {
     }
 }
 
+impl IntoGeoJson for MatchRecords {
+    fn to_geojson<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |r| Some((r.longitude, r.latitude)), path)
+    }
+}
+
+/// The `MatchAssignment` struct holds the results of a one-to-one assignment from
+/// [`MatchRecords::assign`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct MatchAssignment {
+    /// The `records` field holds one match record for each subject address.
+    pub records: MatchRecords,
+    /// The `unassigned` field holds a record for each comparison address that matched a subject
+    /// address, but was not paired with one.
+    pub unassigned: MatchRecords,
+    /// The `unpaired` field holds the positions in the comparison set of the addresses not paired
+    /// with any subject address, whether or not they matched one, in ascending order.
+    pub unpaired: Vec<usize>,
+}
+
 /// The `MatchPartialRecord` struct contains match data for a [`PartialAddress`].
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct MatchPartialRecord {
@@ -324,19 +962,31 @@ pub struct MatchPartialRecord {
 impl MatchPartialRecord {
     /// The `coincident` method attempts to match fields present in the partial address against the
     /// comparison address, returning a `MatchPartialRecord` if successful.  Returns `None` if
-    /// the match status is "missing".
+    /// the match status is "missing".  If the partial address holds an address range, any address
+    /// number within the range matches, and if it holds a unit range, any unit within the range
+    /// matches.
     pub fn coincident<T: Address + Geographic>(
         partial: &PartialAddress,
         address: &T,
     ) -> Option<MatchPartialRecord> {
         let mut match_status = MatchStatus::Missing;
 
-        if let Some(value) = partial.address_number {
+        if let Some(range) = partial.address_number_range() {
+            if range.contains(address.number()) {
+                match_status = MatchStatus::Matching;
+            }
+        } else if let Some(value) = partial.address_number {
             if value == address.number() {
                 match_status = MatchStatus::Matching;
             }
         }
 
+        if &partial.address_number_suffix != address.number_suffix()
+            && match_status == MatchStatus::Matching
+        {
+            match_status = MatchStatus::Missing;
+        }
+
         if &partial.street_name_pre_directional != address.directional()
             && match_status == MatchStatus::Matching
         {
@@ -344,7 +994,13 @@ impl MatchPartialRecord {
         }
 
         if let Some(value) = &partial.street_name {
-            if value != address.street_name() && match_status == MatchStatus::Matching {
+            // An absent pre type in the partial address matches any pre type.
+            let pre_type = partial.pre_type.or(*address.street_name_pre_type());
+            if !StreetNamePreType::same_street(
+                (pre_type, value),
+                (*address.street_name_pre_type(), address.street_name()),
+            ) && match_status == MatchStatus::Matching
+            {
                 match_status = MatchStatus::Missing;
             }
         }
@@ -357,7 +1013,30 @@ impl MatchPartialRecord {
             }
         }
 
-        if &partial.subaddress_identifier() != address.subaddress_id()
+        if &partial.street_name_post_directional != address.street_name_post_directional()
+            && match_status == MatchStatus::Matching
+        {
+            match_status = MatchStatus::Missing;
+        }
+
+        let units = partial.subaddress_identifiers();
+        let unit_matches = match address.subaddress_id() {
+            Some(id) => units.contains(id),
+            None => units.is_empty(),
+        };
+        if !unit_matches && match_status == MatchStatus::Matching {
+            match_status = MatchStatus::Divergent;
+        }
+
+        if partial.building.is_some()
+            && &partial.building != address.building()
+            && match_status == MatchStatus::Matching
+        {
+            match_status = MatchStatus::Divergent;
+        }
+
+        if partial.floor.is_some()
+            && &partial.floor != address.floor()
             && match_status == MatchStatus::Matching
         {
             match_status = MatchStatus::Divergent;
@@ -396,17 +1075,53 @@ impl MatchPartialRecord {
     pub fn compare<T: Address + Geographic>(
         partial: &PartialAddress,
         addresses: &[T],
+    ) -> MatchPartialRecords {
+        Self::from_candidates(partial, addresses)
+    }
+
+    /// The `indexed` method produces the same [`MatchPartialRecords`] as
+    /// [`MatchPartialRecord::compare`], but only compares the partial address against the
+    /// candidates returned by `index`.  If the partial address has no address number, no address
+    /// can match, and the method returns a missing record.  For an address range, gathers the
+    /// candidates with an address number within the range.
+    pub fn indexed<T: Address + Geographic>(
+        partial: &PartialAddress,
+        index: &AddressIndex<T>,
+    ) -> MatchPartialRecords {
+        let street_name = partial.street_name.as_deref();
+        let candidates = match (partial.address_number_range(), partial.address_number) {
+            (Some(range), _) => index.candidates_in_range(&range, partial.pre_type, street_name),
+            (None, Some(number)) => match street_name {
+                Some(street_name) => index.candidates(number, partial.pre_type, street_name),
+                None => index.candidates_by_number(number),
+            },
+            (None, None) => Vec::new(),
+        };
+        Self::from_candidates(partial, candidates)
+    }
+
+    /// The `from_candidates` method attempts to match the partial address against each address in
+    /// `candidates`, returning a [`MatchPartialRecords`].  A non-physical mailing address has no
+    /// candidates, and returns a record with status [`MatchStatus::NonPhysical`].
+    fn from_candidates<'a, T: Address + Geographic + 'a>(
+        partial: &PartialAddress,
+        candidates: impl IntoIterator<Item = &'a T>,
     ) -> MatchPartialRecords {
         let mut records = Vec::new();
-        for address in addresses {
+        for address in candidates {
             let coincident = MatchPartialRecord::coincident(partial, address);
             if let Some(record) = coincident {
                 records.push(record);
             }
         }
         if records.is_empty() {
+            let match_status = if partial.is_physical() {
+                MatchStatus::Missing
+            } else {
+                MatchStatus::NonPhysical
+            };
             records.push(MatchPartialRecord {
-                match_status: MatchStatus::Missing,
+                match_status,
                 address_label: partial.label(),
                 other_label: None,
                 longitude: None,
@@ -457,18 +1172,20 @@ pub struct MatchPartialRecords(Vec<MatchPartialRecord>);
 impl MatchPartialRecords {
     /// For each partial address in `self_addresses`, the `compare` method attempts to match the
     /// fields present in the partial address against the addresses in `other_addresses`, returning
-    /// a `MatchPartialRecords`.
+    /// a `MatchPartialRecords`.  Builds an [`AddressIndex`] over `other_addresses` once, so each
+    /// partial address is only compared against candidates with the same address number.
     pub fn compare<T: Address + Geographic + Send + Sync>(
         self_addresses: &PartialAddresses,
         other_addresses: &[T],
     ) -> Self {
+        let index = AddressIndex::new(other_addresses);
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
         )
         .unwrap();
         let record = self_addresses
             .par_iter()
-            .map(|address| MatchPartialRecord::compare(address, other_addresses))
+            .map(|address| MatchPartialRecord::indexed(address, &index))
             .progress_with_style(style)
             .collect::<Vec<MatchPartialRecords>>();
         let mut records = Vec::new();
@@ -480,11 +1197,12 @@ impl MatchPartialRecords {
 
     /// The `filter` method returns the subset of `PartialMatchRecords` that meet the filter requirement.
     /// The `filter` parameter takes a string reference that can take the values "matching",
-    /// "missing", or "divergent".  The return records contain those records where the match status equals the
+    /// "missing", "divergent" or "nonphysical".  The return records contain those records where the match status equals the
     /// filter value.
     pub fn filter(mut self, filter: &str) -> Self {
         match filter {
             "missing" => self.retain(|r| r.match_status == MatchStatus::Missing),
+            "nonphysical" => self.retain(|r| r.match_status == MatchStatus::NonPhysical),
             "divergent" => self.retain(|r| r.match_status == MatchStatus::Divergent),
             "matching" => self.retain(|r| r.match_status == MatchStatus::Matching),
             _ => info!("Invalid filter provided."),
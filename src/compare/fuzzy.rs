@@ -0,0 +1,25 @@
+//! The `fuzzy` module contains similarity measures used to pair addresses whose street names are
+//! spelled differently, for the fuzzy comparison mode of [`crate::MatchRecords`].
+
+/// The `street_name_similarity` function scores the similarity of two street names on a scale
+/// from 0.0 (nothing in common) to 1.0 (identical), using the normalized Levenshtein edit distance.
+/// The names are compared both as written and with whitespace removed, and the higher score is
+/// returned, so that "MC LEAN" and "MCLEAN" score as identical.
+///
+/// ```
+/// use destination::street_name_similarity;
+///
+/// assert_eq!(street_name_similarity("MC LEAN", "MCLEAN"), 1.0);
+/// assert!(street_name_similarity("FRANKLIN", "FRANKLYN") > 0.8);
+/// assert!(street_name_similarity("FRANKLIN", "REDWOOD") < 0.5);
+/// ```
+#[tracing::instrument]
+pub fn street_name_similarity(from: &str, to: &str) -> f64 {
+    let from = from.trim().to_uppercase();
+    let to = to.trim().to_uppercase();
+    let spaced = strsim::normalized_levenshtein(&from, &to);
+    let from = from.split_whitespace().collect::<String>();
+    let to = to.split_whitespace().collect::<String>();
+    let collapsed = strsim::normalized_levenshtein(&from, &to);
+    spaced.max(collapsed)
+}
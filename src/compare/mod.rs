@@ -1,6 +1,14 @@
 //! The `compare` module contains data types and methods for comparing addresses.
+mod address_index;
 mod compare_fire;
 mod eponym;
+mod fuzzy;
+mod policy;
+mod reconcile;
 
+pub use address_index::*;
 pub use compare_fire::*;
 pub use eponym::*;
+pub use fuzzy::*;
+pub use policy::*;
+pub use reconcile::*;
@@ -0,0 +1,200 @@
+//! The `policy` module holds the rules deciding which address components must agree for two
+//! addresses to be coincident, used by [`crate::Address::coincident_with`].
+use crate::{AddressErrorKind, Io};
+use std::path::Path;
+
+/// The `ComponentPolicy` enum sets how a comparison treats a difference in an address component.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+    derive_more::FromStr,
+    strum::EnumIter,
+)]
+pub enum ComponentPolicy {
+    /// The component must agree for the addresses to be coincident.
+    #[default]
+    Required,
+    /// The addresses can be coincident when the component differs, and the difference is reported
+    /// as a [`crate::Mismatch`].
+    Reported,
+    /// Differences in the component are ignored.
+    Ignored,
+}
+
+impl ComponentPolicy {
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `ComponentPolicy`.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "required" | "require" => Some(Self::Required),
+            "reported" | "report" | "mismatch" => Some(Self::Reported),
+            "ignored" | "ignore" => Some(Self::Ignored),
+            _ => None,
+        }
+    }
+}
+
+/// The `MatchPolicy` struct sets the [`ComponentPolicy`] of each address component when comparing
+/// addresses.  The default policy requires every component of the address label, postal community,
+/// zip code and state to agree, reports differences in the subaddress type, floor, building and
+/// status, and ignores the distance between address points.
+///
+/// Policies read from TOML with one key per field, e.g. `zip = "Ignored"`, and omitted fields
+/// take their default value.
+///
+/// The policy applies to comparisons between complete addresses, in [`crate::MatchRecords`] and
+/// [`crate::ReconcileRecords`].  Partial addresses in [`crate::MatchPartialRecords`], including
+/// the business addresses of [`crate::FireInspectionMatches`], and the business licenses in
+/// [`crate::BusinessMatchRecords`] only compare the components they hold, and do not take a
+/// policy.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct MatchPolicy {
+    /// The `number` field sets the policy for the address number.
+    pub number: ComponentPolicy,
+    /// The `number_suffix` field sets the policy for the address number suffix.
+    pub number_suffix: ComponentPolicy,
+    /// The `directional` field sets the policy for the street name pre directional.
+    pub directional: ComponentPolicy,
+    /// The `pre_modifier` field sets the policy for the street name pre modifier.
+    pub pre_modifier: ComponentPolicy,
+    /// The `street_name` field sets the policy for the street name and street name pre type,
+    /// which compare together using [`crate::StreetNamePreType::same_street`].
+    pub street_name: ComponentPolicy,
+    /// The `separator` field sets the policy for the separator element.
+    pub separator: ComponentPolicy,
+    /// The `street_type` field sets the policy for the street name post type.
+    pub street_type: ComponentPolicy,
+    /// The `post_directional` field sets the policy for the street name post directional.
+    pub post_directional: ComponentPolicy,
+    /// The `subaddress_type` field sets the policy for the subaddress type.
+    pub subaddress_type: ComponentPolicy,
+    /// The `subaddress_id` field sets the policy for the subaddress identifier.
+    pub subaddress_id: ComponentPolicy,
+    /// The `floor` field sets the policy for the floor number.
+    pub floor: ComponentPolicy,
+    /// The `building` field sets the policy for the building identifier.
+    pub building: ComponentPolicy,
+    /// The `zip` field sets the policy for the zip code.
+    pub zip: ComponentPolicy,
+    /// The `postal_community` field sets the policy for the postal community.
+    pub postal_community: ComponentPolicy,
+    /// The `state` field sets the policy for the state.
+    pub state: ComponentPolicy,
+    /// The `status` field sets the policy for the address status.
+    pub status: ComponentPolicy,
+    /// The `drift` field sets the policy for the distance between the points of matching
+    /// addresses.  Unless ignored, match records report the distance, and a distance greater than
+    /// `max_drift` is a mismatch.
+    pub drift: ComponentPolicy,
+    /// The `max_drift` field holds the greatest distance allowed between the points of matching
+    /// addresses, in meters as measured by [`crate::Geographic::haversine`].  When absent, the
+    /// distance is reported but never a mismatch.
+    pub max_drift: Option<f64>,
+}
+
+impl Default for MatchPolicy {
+    fn default() -> Self {
+        Self {
+            number: ComponentPolicy::Required,
+            number_suffix: ComponentPolicy::Required,
+            directional: ComponentPolicy::Required,
+            pre_modifier: ComponentPolicy::Required,
+            street_name: ComponentPolicy::Required,
+            separator: ComponentPolicy::Required,
+            street_type: ComponentPolicy::Required,
+            post_directional: ComponentPolicy::Required,
+            subaddress_type: ComponentPolicy::Reported,
+            subaddress_id: ComponentPolicy::Required,
+            floor: ComponentPolicy::Reported,
+            building: ComponentPolicy::Reported,
+            zip: ComponentPolicy::Required,
+            postal_community: ComponentPolicy::Required,
+            state: ComponentPolicy::Required,
+            status: ComponentPolicy::Reported,
+            drift: ComponentPolicy::Ignored,
+            max_drift: None,
+        }
+    }
+}
+
+impl MatchPolicy {
+    /// The `county` method returns the policy for comparing against county addresses, which
+    /// ignores differences in the zip code and postal community, since the county assigns these
+    /// by a different boundary than the post office.
+    pub fn county() -> Self {
+        Self {
+            zip: ComponentPolicy::Ignored,
+            postal_community: ComponentPolicy::Ignored,
+            ..Default::default()
+        }
+    }
+
+    /// The `strict` method returns the policy requiring every component to agree, including the
+    /// subaddress type, floor, building and status.  The distance between address points is still
+    /// ignored, since no maximum distance applies to every source.
+    pub fn strict() -> Self {
+        Self {
+            subaddress_type: ComponentPolicy::Required,
+            floor: ComponentPolicy::Required,
+            building: ComponentPolicy::Required,
+            status: ComponentPolicy::Required,
+            ..Default::default()
+        }
+    }
+
+    /// The `with_drift` method returns the policy with a mismatch reported for matching addresses
+    /// with points farther apart than `max_drift` meters.
+    pub fn with_drift(self, max_drift: f64) -> Self {
+        Self {
+            drift: ComponentPolicy::Reported,
+            max_drift: Some(max_drift),
+            ..self
+        }
+    }
+
+    /// The `match_mixed` method attempts to match the string `input` against the name of a
+    /// predefined policy: "default", "county" or "strict".
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "default" => Some(Self::default()),
+            "county" => Some(Self::county()),
+            "strict" => Some(Self::strict()),
+            _ => None,
+        }
+    }
+
+    /// The `from_path` method reads a policy from the TOML file at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, AddressErrorKind> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| Io::new(path.into(), source, line!(), file!().into()))?;
+        let policy = toml::from_str::<Self>(&text).map_err(|issue| {
+            let source = std::io::Error::new(std::io::ErrorKind::InvalidData, issue.to_string());
+            Io::new(path.into(), source, line!(), file!().into())
+        })?;
+        tracing::info!("Match policy read from {:?}.", path);
+        Ok(policy)
+    }
+
+    /// The `from_name_or_path` method returns the predefined policy named `input`, or else reads
+    /// the policy from the TOML file at path `input`.
+    pub fn from_name_or_path(input: &str) -> Result<Self, AddressErrorKind> {
+        match Self::match_mixed(input) {
+            Some(policy) => Ok(policy),
+            None => Self::from_path(input),
+        }
+    }
+}
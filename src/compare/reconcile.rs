@@ -0,0 +1,240 @@
+//! The `reconcile` module compares two sets of addresses in both directions, reporting the
+//! addresses found in only one set alongside the matched pairs.
+use crate::{
+    from_csv, to_csv, to_geojson, Address, AddressErrorKind, Geographic, IntoCsv, IntoGeoJson, Io,
+    MatchPolicy, MatchRecord, MatchRecords, MatchStatus, Validate,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+
+/// The `ReconcileStatus` enum classifies a record of a reconciliation between a source and a
+/// target set of addresses.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+)]
+pub enum ReconcileStatus {
+    /// The source address has a match in the target set.
+    Matching,
+    /// The source address has a match in the target set, but differs in the components reported
+    /// by the match policy.
+    Divergent,
+    /// The source address has no match in the target set.
+    #[default]
+    SourceOnly,
+    /// The target address has no match in the source set.
+    TargetOnly,
+}
+
+impl ReconcileStatus {
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `ReconcileStatus`.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().replace([' ', '-'], "_").as_str() {
+            "matching" => Some(Self::Matching),
+            "divergent" => Some(Self::Divergent),
+            "source_only" | "source" => Some(Self::SourceOnly),
+            "target_only" | "target" => Some(Self::TargetOnly),
+            _ => None,
+        }
+    }
+}
+
+/// The `ReconcileRecord` struct reports one row of a reconciliation: a matched pair of addresses,
+/// or an address found in only one of the sets.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ReconcileRecord {
+    /// The `status` field holds the classification of the record.
+    pub status: ReconcileStatus,
+    /// The `source_label` field holds the label of the source address, if any.
+    pub source_label: Option<String>,
+    /// The `target_label` field holds the label of the target address, if any.
+    pub target_label: Option<String>,
+    /// The `mismatches` field lists the differences between a divergent pair, e.g.
+    /// "zip: 97526 not equal to 97527".
+    pub mismatches: Option<String>,
+    /// The `longitude` field holds the 'x' value of the source address, or of the target address
+    /// for a target-only record.
+    pub longitude: f64,
+    /// The `latitude` field holds the 'y' value of the source address, or of the target address
+    /// for a target-only record.
+    pub latitude: f64,
+}
+
+impl ReconcileRecord {
+    /// The `paired` method converts the match record `record` of a source address into a
+    /// reconciliation record.
+    fn paired(record: &MatchRecord) -> Self {
+        let status = match record.match_status {
+            MatchStatus::Matching => ReconcileStatus::Matching,
+            MatchStatus::Divergent => ReconcileStatus::Divergent,
+            _ => ReconcileStatus::SourceOnly,
+        };
+        let mismatches = record
+            .mismatches()
+            .iter()
+            .map(|(component, message)| format!("{component}: {message}"))
+            .collect::<Vec<String>>();
+        Self {
+            status,
+            source_label: Some(record.address_label.clone()),
+            target_label: record.other_label.clone(),
+            mismatches: (!mismatches.is_empty()).then(|| mismatches.join("; ")),
+            longitude: record.longitude,
+            latitude: record.latitude,
+        }
+    }
+
+    /// The `target_only` method converts the target address `address` without a match into a
+    /// reconciliation record.
+    fn target_only<T: Address + Geographic>(address: &T) -> Self {
+        Self {
+            status: ReconcileStatus::TargetOnly,
+            target_label: Some(address.label()),
+            longitude: address.longitude(),
+            latitude: address.latitude(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Geographic for ReconcileRecord {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+/// The `ReconcileSummary` struct counts the records of a reconciliation by status.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct ReconcileSummary {
+    /// The `matching` field counts the matching pairs.
+    pub matching: usize,
+    /// The `divergent` field counts the divergent pairs.
+    pub divergent: usize,
+    /// The `source_only` field counts the source addresses without a match.
+    pub source_only: usize,
+    /// The `target_only` field counts the target addresses without a match.
+    pub target_only: usize,
+}
+
+impl ReconcileSummary {
+    /// The `count` method returns the number of records with status `status`.
+    pub fn count(&self, status: ReconcileStatus) -> usize {
+        match status {
+            ReconcileStatus::Matching => self.matching,
+            ReconcileStatus::Divergent => self.divergent,
+            ReconcileStatus::SourceOnly => self.source_only,
+            ReconcileStatus::TargetOnly => self.target_only,
+        }
+    }
+}
+
+impl std::fmt::Display for ReconcileSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use strum::IntoEnumIterator;
+        writeln!(f, "{:<12} {:>8}", "Status", "Count")?;
+        for status in ReconcileStatus::iter() {
+            writeln!(f, "{:<12} {:>8}", status.to_string(), self.count(status))?;
+        }
+        Ok(())
+    }
+}
+
+/// The `ReconcileRecords` struct holds a vector of type [`ReconcileRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct ReconcileRecords(Vec<ReconcileRecord>);
+
+impl ReconcileRecords {
+    /// The `reconcile` method pairs each address in `source` with at most one address in
+    /// `target`, using [`MatchRecords::assign`] and the match policy `policy`.  The records hold
+    /// each matched pair from the source side, followed by the source addresses without a pair,
+    /// then the target addresses not paired with any source address.
+    pub fn reconcile<
+        T: Validate + Geographic + Send + Sync,
+        U: Validate + Geographic + Send + Sync,
+    >(
+        source: &[T],
+        target: &[U],
+        policy: &MatchPolicy,
+    ) -> Self {
+        tracing::info!("Comparing source to target.");
+        let assignment = MatchRecords::assign(source, target, policy);
+        let (paired, unpaired): (Vec<&MatchRecord>, Vec<&MatchRecord>) =
+            assignment.records.iter().partition(|record| {
+                matches!(
+                    record.match_status,
+                    MatchStatus::Matching | MatchStatus::Divergent
+                )
+            });
+        let target_only = assignment
+            .unpaired
+            .iter()
+            .map(|j| ReconcileRecord::target_only(&target[*j]));
+        let records = paired
+            .into_iter()
+            .chain(unpaired)
+            .map(ReconcileRecord::paired)
+            .chain(target_only)
+            .collect::<Vec<ReconcileRecord>>();
+        Self(records)
+    }
+
+    /// The `summary` method counts the records by status.
+    pub fn summary(&self) -> ReconcileSummary {
+        let mut summary = ReconcileSummary::default();
+        for record in self.iter() {
+            match record.status {
+                ReconcileStatus::Matching => summary.matching += 1,
+                ReconcileStatus::Divergent => summary.divergent += 1,
+                ReconcileStatus::SourceOnly => summary.source_only += 1,
+                ReconcileStatus::TargetOnly => summary.target_only += 1,
+            }
+        }
+        summary
+    }
+
+    /// The `filter` method returns the records with the status named by `filter`, see
+    /// [`ReconcileStatus::match_mixed`].
+    pub fn filter(mut self, filter: &str) -> Self {
+        match ReconcileStatus::match_mixed(filter) {
+            Some(status) => self.retain(|r| r.status == status),
+            None => tracing::info!("Invalid filter provided."),
+        }
+        self
+    }
+}
+
+impl IntoCsv<ReconcileRecords> for ReconcileRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+impl IntoGeoJson for ReconcileRecords {
+    fn to_geojson<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |r| Some((r.longitude, r.latitude)), path)
+    }
+}
@@ -0,0 +1,312 @@
+//! The `diagnostics` module reports how [`Parse::address`] read an address, so that parses with
+//! unused text or ambiguous components can be reviewed instead of trusted blindly.
+use crate::{Parse, PartialAddress, StreetNamePostType};
+use nom::IResult;
+
+/// The `AddressComponent` enum names the component of an address assigned to a span of the
+/// parsed input.
+#[allow(missing_docs)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+)]
+pub enum AddressComponent {
+    AddressNumber,
+    AddressNumberSuffix,
+    PreDirectional,
+    PreModifier,
+    PreType,
+    Separator,
+    StreetName,
+    PostType,
+    PostDirectional,
+    Building,
+    Floor,
+    SubaddressType,
+    SubaddressIdentifier,
+    PostalDelivery,
+    PostalCommunity,
+    State,
+    Zip,
+}
+
+/// The `Ambiguity` enum describes a decision made by the parser where the input supports more
+/// than one reading.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+)]
+pub enum Ambiguity {
+    /// A spelled out directional word such as "WEST" could be a directional or part of the street
+    /// name (e.g. "WEST ST" or "WEST SIDE RD").
+    DirectionalOrStreetName,
+    /// The street name contains a word that is also a street name post type (e.g. "VIEW" in
+    /// "MOUNTAIN VIEW AVE").
+    PostTypeOrStreetName,
+    /// The post type is also a valid subaddress type, so the word could belong to the subaddress.
+    PostTypeOrSubaddress,
+}
+
+/// The `Confidence` enum rates how much to trust a parsed component.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+)]
+pub enum Confidence {
+    /// The `High` variant indicates the component has a single plausible reading.
+    #[default]
+    High,
+    /// The `Low` variant indicates the parser resolved an [`Ambiguity`] to produce the component.
+    Low,
+}
+
+/// The `ParsedComponent` struct records the portion of the input assigned to a component of the
+/// address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct ParsedComponent {
+    /// The `component` field identifies the address component.
+    pub component: AddressComponent,
+    /// The `text` field holds the input text assigned to the component.
+    pub text: String,
+    /// The `start` field is the byte offset in the input where the component begins.
+    pub start: usize,
+    /// The `end` field is the byte offset in the input where the component ends (exclusive).
+    pub end: usize,
+    /// The `ambiguity` field holds the ambiguous decision made to produce the component, if any.
+    pub ambiguity: Option<Ambiguity>,
+}
+
+impl ParsedComponent {
+    /// The `confidence` method returns [`Confidence::Low`] if the component resulted from an
+    /// ambiguous decision, and [`Confidence::High`] otherwise.
+    pub fn confidence(&self) -> Confidence {
+        match self.ambiguity {
+            Some(_) => Confidence::Low,
+            None => Confidence::High,
+        }
+    }
+}
+
+/// The `ParseToken` struct holds a word from the input that the parser did not assign to any
+/// component, along with its byte span.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct ParseToken {
+    /// The `text` field holds the unused word.
+    pub text: String,
+    /// The `start` field is the byte offset in the input where the word begins.
+    pub start: usize,
+    /// The `end` field is the byte offset in the input where the word ends (exclusive).
+    pub end: usize,
+}
+
+/// The `ParseDiagnostics` struct holds the result of [`Parse::address_diagnostics`].  Along with
+/// the parsed address, it lists each component with its span in the input, and any words from
+/// the input that the parser read but could not use, or did not read at all.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct ParseDiagnostics {
+    /// The `address` field holds the parsed address.
+    pub address: PartialAddress,
+    /// The `components` field lists the parsed components in the order they appear in the input.
+    pub components: Vec<ParsedComponent>,
+    /// The `unconsumed` field lists words from the input not assigned to any component.
+    pub unconsumed: Vec<ParseToken>,
+}
+
+impl ParseDiagnostics {
+    /// The `is_confident` method returns true if every word in the input was assigned to a
+    /// component, and no component has [`Confidence::Low`].
+    pub fn is_confident(&self) -> bool {
+        self.unconsumed.is_empty()
+            && self
+                .components
+                .iter()
+                .all(|c| c.confidence() == Confidence::High)
+    }
+
+    /// The `component` method returns the parsed component of kind `component`, if present.
+    pub fn component(&self, component: AddressComponent) -> Option<&ParsedComponent> {
+        self.components.iter().find(|c| c.component == component)
+    }
+
+    /// The `ambiguities` method returns the components with an ambiguous reading.
+    pub fn ambiguities(&self) -> Vec<&ParsedComponent> {
+        self.components
+            .iter()
+            .filter(|c| c.ambiguity.is_some())
+            .collect()
+    }
+
+    /// The `record` method notes the text consumed by a parser, moving from `before` to `after`
+    /// in `input`.  If the parser produced a value (`found` is true), the text is recorded as
+    /// `component`, otherwise any consumed words are recorded as unconsumed tokens, because the
+    /// parser read them without using them.
+    fn record(
+        &mut self,
+        input: &str,
+        before: &str,
+        after: &str,
+        component: AddressComponent,
+        found: bool,
+    ) {
+        let Some((start, text)) = Self::span(input, before, after) else {
+            return;
+        };
+        if found {
+            self.components.push(ParsedComponent {
+                component,
+                text: text.to_string(),
+                start,
+                end: start + text.len(),
+                ambiguity: None,
+            });
+        } else {
+            tracing::trace!("{component} parser discarded {text}");
+            self.unconsumed.extend(Self::tokens(start, text));
+        }
+    }
+
+    /// The `span` method returns the byte offset in `input` and text of the portion consumed
+    /// moving from `before` to `after`, trimmed of whitespace and commas, or `None` if nothing
+    /// but whitespace and commas was consumed.
+    fn span<'a>(input: &str, before: &'a str, after: &str) -> Option<(usize, &'a str)> {
+        let consumed = &before[..before.len().saturating_sub(after.len())];
+        let trimmed = consumed.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        let start = input.len() - before.len() + consumed.len() - trimmed.len();
+        let text = trimmed.trim_end_matches(|c: char| c.is_whitespace() || c == ',');
+        if text.is_empty() {
+            None
+        } else {
+            Some((start, text))
+        }
+    }
+
+    /// The `tokens` method splits `rest`, found at byte offset `offset` of the input, into words
+    /// delimited by whitespace or commas, with byte spans relative to the input.
+    fn tokens(offset: usize, rest: &str) -> Vec<ParseToken> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (i, c) in rest.char_indices() {
+            let delimiter = c.is_whitespace() || c == ',';
+            match (start, delimiter) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    tokens.push(ParseToken {
+                        text: rest[s..i].to_string(),
+                        start: offset + s,
+                        end: offset + i,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            tokens.push(ParseToken {
+                text: rest[s..].to_string(),
+                start: offset + s,
+                end: offset + rest.len(),
+            });
+        }
+        tokens
+    }
+
+    /// The `flag` method marks the most recently recorded component of kind `component` with
+    /// `ambiguity`.
+    fn flag(&mut self, component: AddressComponent, ambiguity: Ambiguity) {
+        if let Some(parsed) = self
+            .components
+            .iter_mut()
+            .rev()
+            .find(|c| c.component == component)
+        {
+            parsed.ambiguity.get_or_insert(ambiguity);
+        }
+    }
+
+    /// The `flag_ambiguities` method marks the recorded components of `address` that admit more
+    /// than one reading.
+    fn flag_ambiguities(&mut self, address: &PartialAddress) {
+        use AddressComponent as C;
+        // A spelled out directional can also be read as a street name, and the parser declines
+        // the directional in cases like "WEST ST".
+        if self
+            .component(C::PreDirectional)
+            .is_some_and(|c| Parse::spelled_directional(&c.text))
+        {
+            self.flag(C::PreDirectional, Ambiguity::DirectionalOrStreetName);
+        }
+        if let Some(street_name) = &address.street_name {
+            if address.street_name_pre_directional.is_none()
+                && address.pre_modifier.is_none()
+                && address.pre_type.is_none()
+                && address.separator.is_none()
+                && Parse::spelled_directional(street_name)
+            {
+                self.flag(C::StreetName, Ambiguity::DirectionalOrStreetName);
+            }
+            if street_name
+                .split_whitespace()
+                .any(|word| StreetNamePostType::match_mixed(word).is_some())
+            {
+                self.flag(C::StreetName, Ambiguity::PostTypeOrStreetName);
+            }
+        }
+        if self
+            .component(C::PostType)
+            .is_some_and(|c| matches!(Parse::subaddress_type(&c.text), Ok((_, Some(_)))))
+        {
+            self.flag(C::PostType, Ambiguity::PostTypeOrSubaddress);
+        }
+    }
+}
+
+impl Parse {
+    /// The `address_diagnostics` function parses the complete address in `input` like
+    /// [`Parse::address`], and reports the span of each component, any words left unused, and any
+    /// ambiguous decisions made along the way.
+    #[tracing::instrument(skip_all)]
+    pub fn address_diagnostics(input: &str) -> IResult<&str, ParseDiagnostics> {
+        let mut diagnostics = ParseDiagnostics::default();
+        let (rem, address) =
+            Self::address_traced(input, &mut |component, before, after, found| {
+                diagnostics.record(input, before, after, component, found)
+            })?;
+        diagnostics
+            .unconsumed
+            .extend(ParseDiagnostics::tokens(input.len() - rem.len(), rem));
+        if !diagnostics.unconsumed.is_empty() {
+            tracing::trace!("Unconsumed input: {:?}", &diagnostics.unconsumed);
+        }
+        diagnostics.flag_ambiguities(&address);
+        diagnostics.address = address;
+        Ok((rem, diagnostics))
+    }
+}
@@ -21,7 +21,7 @@ macro_rules! impl_address_error {
     };
 }
 
-impl_address_error!(Bincode, Io, Nom);
+impl_address_error!(Bincode, Io, Json, LineWidth, Nom);
 
 /// The `AddressErrorKind` enum contains the individual error type associated with the library operation.
 #[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
@@ -38,6 +38,12 @@ pub enum AddressErrorKind {
     /// The `Io` variant contains an [`Io`] error.
     #[from(Io)]
     Io(Io),
+    /// The `Json` variant contains a [`Json`] error.
+    #[from(Json)]
+    Json(Json),
+    /// The `LineWidth` variant contains a [`LineWidth`] error.
+    #[from(LineWidth)]
+    LineWidth(LineWidth),
     /// The `Nom` variant contains an [`Nom`] error.
     #[from(Nom)]
     Nom(Nom),
@@ -63,6 +69,16 @@ pub struct Csv {
     file: String,
 }
 
+/// The `Json` struct contains error information associated with the `serde_json` crate.
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_new::new)]
+#[display("json error at path {path:?} in line {line} of {file}")]
+pub struct Json {
+    path: std::path::PathBuf,
+    source: serde_json::Error,
+    line: u32,
+    file: String,
+}
+
 /// The `Bincode` struct contains error information associated with the `bincode` crate.
 #[derive(Debug, derive_more::Display, derive_more::Error, derive_new::new)]
 #[display("bincode error at line {line} in {file}")]
@@ -88,6 +104,18 @@ impl std::error::Error for Builder {
     }
 }
 
+/// The `LineWidth` struct contains error information about a line of text too long to print in
+/// the format `target`.
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_new::new)]
+#[display("{target} line exceeds {limit} characters: {text} in line {line} of {file}")]
+pub struct LineWidth {
+    text: String,
+    limit: usize,
+    target: String,
+    line: u32,
+    file: String,
+}
+
 /// The `Nom` struct contains error information associated with the `nom` crate.
 #[derive(Debug, derive_more::Display, derive_more::Error)]
 #[display("nom parsing error: {description} in line {line} of {file}")]
@@ -1,9 +1,10 @@
 //! The `geo` module defines spatial address types, and implements traits from the `galileo` crate for these types.
 use crate::{
-    from_bin, to_bin, Address, AddressDelta, AddressDeltas, AddressError, AddressErrorKind,
-    AddressStatus, Addresses, Bincode, CommonAddress, IntoBin, State, StreetNamePostType,
-    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
-    SubaddressType,
+    from_bin, to_bin, to_geojson, Address, AddressDelta, AddressDeltas, AddressError,
+    AddressErrorKind, AddressIndex, AddressStatus, Addresses, Bincode, CommonAddress, Csv, IntoBin,
+    IntoGeoJson, Io, MatchPartialRecord, MatchPartialRecords, Parse, PartialAddress,
+    PartialAddresses, Point, Projection, State, StreetNamePostType, StreetNamePreDirectional,
+    StreetNamePreModifier, StreetNamePreType, StreetSeparator, SubaddressType,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
@@ -17,8 +18,29 @@ pub trait Geographic {
     fn latitude(&self) -> f64;
     /// The `longitude` method returns the longitude component of the geographic coordinates.
     fn longitude(&self) -> f64;
+
+    /// The `project` method returns the geographic coordinates converted to the coordinate system
+    /// `projection`, as a [`Point`].  Projecting values from mixed sources to the same coordinate
+    /// system allows measuring the distance between them using [`Cartesian::distance`].
+    fn project(&self, projection: Projection) -> Point {
+        Point::new(self.longitude(), self.latitude(), Projection::Wgs84).to(projection)
+    }
+
+    /// The `haversine` method returns the great-circle distance in meters between `self` and
+    /// `other`.
+    fn haversine<T: Geographic + ?Sized>(&self, other: &T) -> f64 {
+        let (lat1, lat2) = (self.latitude().to_radians(), other.latitude().to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_lon = (other.longitude() - self.longitude()).to_radians();
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
 }
 
+/// The mean radius of the earth in meters, used by [`Geographic::haversine`].
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
 /// The type can produce cartesian coordinates.
 pub trait Cartesian {
     /// The `x` method returns the cartesian X portion of the projected coordinates of the address.
@@ -161,6 +183,14 @@ impl Address for GeoAddress {
         &mut self.address.street_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.address.post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.address.post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.address.subaddress_id
     }
@@ -266,6 +296,106 @@ pub struct GeoAddresses(Vec<GeoAddress>);
 
 impl Addresses<GeoAddress> for GeoAddresses {}
 
+impl GeoAddresses {
+    /// The `geocode` method matches each partial address in `addresses` against the reference
+    /// addresses in `self`, returning one [`MatchPartialRecord`] per partial address, in the same
+    /// order.  Where a partial address matches more than one reference address (e.g. a parent
+    /// address without a subaddress matching several units), the record holds the first
+    /// match, preferring exact matches over divergent ones.
+    pub fn geocode(&self, addresses: &PartialAddresses) -> MatchPartialRecords {
+        let index = AddressIndex::new(&self[..]);
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Geocoding addresses.'}",
+        )
+        .unwrap();
+        let records = addresses
+            .par_iter()
+            .map(|address| MatchPartialRecord::indexed(address, &index)[0].clone())
+            .progress_with_style(style)
+            .collect::<Vec<MatchPartialRecord>>();
+        MatchPartialRecords::new(records)
+    }
+
+    /// The `geocode_csv` method reads the CSV file at `path`, parses the free-text addresses in
+    /// the field named `column` using [`Parse::address`], and matches them against the reference
+    /// addresses in `self` using [`GeoAddresses::geocode`].  Writes each input row to a CSV file at
+    /// `output`, with the fields `latitude`, `longitude`, `matched_label` and `match_status` added.
+    /// Addresses that fail to parse are reported as missing.
+    pub fn geocode_csv<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        path: P,
+        column: &str,
+        output: Q,
+    ) -> Result<(), AddressErrorKind> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = csv::Reader::from_path(&path)
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        let headers = reader
+            .headers()
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?
+            .clone();
+        let Some(position) = headers.iter().position(|header| header == column) else {
+            let source = std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("column {column} not found"),
+            );
+            return Err(Io::new(path, source, line!(), file!().into()).into());
+        };
+        let mut rows = Vec::new();
+        let mut partials = Vec::new();
+        for result in reader.records() {
+            let row =
+                result.map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+            let text = row.get(position).unwrap_or_default();
+            let partial = match Parse::address(text) {
+                Ok((_, address)) => address,
+                Err(e) => {
+                    tracing::warn!("Could not parse {text}: {e}");
+                    PartialAddress::default()
+                }
+            };
+            partials.push(partial);
+            rows.push(row);
+        }
+        tracing::info!("{} addresses read from {:?}.", rows.len(), &path);
+        let matches = self.geocode(&PartialAddresses::new(partials));
+
+        let output = output.as_ref().to_path_buf();
+        let mut writer = csv::Writer::from_path(&output)
+            .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+        let mut header = headers.clone();
+        header.extend(["latitude", "longitude", "matched_label", "match_status"]);
+        writer
+            .write_record(&header)
+            .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+        for (row, record) in rows.iter().zip(matches.iter()) {
+            let mut row = row.clone();
+            row.push_field(&record.latitude().map(|v| v.to_string()).unwrap_or_default());
+            row.push_field(
+                &record
+                    .longitude()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+            );
+            row.push_field(&record.other_label().unwrap_or_default());
+            row.push_field(&format!("{:?}", record.match_status()));
+            writer
+                .write_record(&row)
+                .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+        }
+        writer
+            .flush()
+            .map_err(|source| Io::new(output.clone(), source, line!(), file!().into()))?;
+        Ok(())
+    }
+}
+
+impl IntoGeoJson for GeoAddresses {
+    fn to_geojson<P: AsRef<Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |a| Some((a.longitude, a.latitude)), path)
+    }
+}
+
 impl IntoBin<GeoAddress> for GeoAddress {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, AddressError> {
         match from_bin(path) {
@@ -369,6 +499,14 @@ impl Address for AddressPoint {
         &mut self.address.street_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.address.post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.address.post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.address.subaddress_id
     }
@@ -566,6 +704,14 @@ impl Address for SpatialAddress {
         &mut self.address.street_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.address.post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.address.post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.address.subaddress_id
     }
@@ -651,6 +797,47 @@ impl Cartesian for SpatialAddress {
     }
 }
 
+impl SpatialAddress {
+    /// The `from_geographic` method creates a `SpatialAddress` from `address` and the geographic
+    /// coordinates `latitude` and `longitude`, computing the cartesian coordinates in the
+    /// coordinate system `projection`.
+    pub fn from_geographic<T: Address>(
+        address: &T,
+        latitude: f64,
+        longitude: f64,
+        projection: Projection,
+    ) -> Self {
+        let (x, y) = projection.from_geographic(longitude, latitude);
+        Self {
+            address: CommonAddress::from(address),
+            latitude,
+            longitude,
+            x,
+            y,
+        }
+    }
+
+    /// The `from_projected` method creates a `SpatialAddress` from `address` and the cartesian
+    /// coordinates `x` and `y` in the coordinate system `projection`, computing the geographic
+    /// coordinates.
+    pub fn from_projected<T: Address>(address: &T, x: f64, y: f64, projection: Projection) -> Self {
+        let (longitude, latitude) = projection.to_geographic(x, y);
+        Self {
+            address: CommonAddress::from(address),
+            latitude,
+            longitude,
+            x,
+            y,
+        }
+    }
+
+    /// The `reproject` method recomputes the cartesian coordinates of the address in the
+    /// coordinate system `projection` from its geographic coordinates.
+    pub fn reproject(&mut self, projection: Projection) {
+        (self.x, self.y) = projection.from_geographic(self.longitude, self.latitude);
+    }
+}
+
 impl<T: Address + Geographic + Cartesian + Clone> From<&T> for SpatialAddress {
     fn from(data: &T) -> Self {
         let address = CommonAddress::from(data);
@@ -685,6 +872,17 @@ pub struct SpatialAddresses(Vec<SpatialAddress>);
 
 impl Addresses<SpatialAddress> for SpatialAddresses {}
 
+impl SpatialAddresses {
+    /// The `reproject` method recomputes the cartesian coordinates of each address in the
+    /// coordinate system `projection`, using [`SpatialAddress::reproject`].  Addresses from
+    /// sources using different projections must share a coordinate system before comparing
+    /// distances.
+    pub fn reproject(&mut self, projection: Projection) {
+        self.iter_mut()
+            .for_each(|address| address.reproject(projection));
+    }
+}
+
 impl IntoBin<SpatialAddresses> for SpatialAddresses {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, AddressError> {
         match from_bin(path) {
@@ -699,6 +897,12 @@ impl IntoBin<SpatialAddresses> for SpatialAddresses {
     }
 }
 
+impl IntoGeoJson for SpatialAddresses {
+    fn to_geojson<P: AsRef<Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |a| Some((a.longitude, a.latitude)), path)
+    }
+}
+
 impl<T: Address + Geographic + Cartesian + Clone + Sized> From<&[T]> for SpatialAddresses {
     fn from(addresses: &[T]) -> Self {
         let records = addresses
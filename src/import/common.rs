@@ -1,9 +1,9 @@
 use crate::{
-    deserialize_arcgis_data, from_bin, from_csv, to_bin, to_csv, AddressError, AddressErrorKind,
-    AddressStatus, Bincode, CommonAddress, CommonAddresses, GeoAddress, GeoAddresses, IntoBin,
-    IntoCsv, Io, SpatialAddress, SpatialAddresses, State, StreetNamePostType,
-    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
-    SubaddressType,
+    deserialize_arcgis_data, from_bin, from_csv, from_geojson, to_bin, to_csv, to_geojson,
+    AddressError, AddressErrorKind, AddressStatus, Bincode, CommonAddress, CommonAddresses,
+    GeoAddress, GeoAddresses, IntoBin, IntoCsv, IntoGeoJson, Io, Projection, SpatialAddress,
+    SpatialAddresses, State, StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier,
+    StreetNamePreType, StreetSeparator, SubaddressType,
 };
 /// The `SpatialAddressRaw` struct defines the fields of a valid address, following the FGDC standard,
 /// with the inclusion of NENA-required fields for emergency response.
@@ -38,6 +38,13 @@ pub struct SpatialAddressRaw {
     /// name.
     #[serde(deserialize_with = "StreetNamePostType::deserialize_mixed")]
     pub street_type: Option<StreetNamePostType>,
+    /// The `post_directional` field represents the street name post directional component of the
+    /// complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed"
+    )]
+    pub post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(deserialize_with = "SubaddressType::deserialize_mixed")]
@@ -69,9 +76,11 @@ pub struct SpatialAddressRaw {
     pub longitude: f64,
     /// The `x` field represents the cartesian X portion of the projected coordinates of the
     /// address.
+    #[serde(default)]
     pub x: f64,
     /// The `y` field represents the cartesian Y portion of the projected coordinates of the
     /// address.
+    #[serde(default)]
     pub y: f64,
 }
 
@@ -86,6 +95,7 @@ impl From<SpatialAddressRaw> for CommonAddress {
             separator: value.separator,
             street_name: value.street_name,
             street_type: value.street_type,
+            post_directional: value.post_directional,
             subaddress_type: value.subaddress_type,
             subaddress_id: value.subaddress_id,
             floor: value.floor,
@@ -180,6 +190,27 @@ impl IntoBin<SpatialAddressesRaw> for SpatialAddressesRaw {
     }
 }
 
+impl SpatialAddressesRaw {
+    /// The `from_geojson` method reads addresses from the point features of a GeoJSON
+    /// FeatureCollection at `path`, with the address fields as feature properties.  The point
+    /// geometry supplies the geographic coordinates when the properties lack them.  When the
+    /// properties lack projected coordinates, computes them in Web Mercator.
+    pub fn from_geojson<P: AsRef<std::path::Path>>(path: P) -> Result<Self, AddressErrorKind> {
+        let mut records = from_geojson::<SpatialAddressRaw, P>(path)?;
+        for record in records.iter_mut().filter(|r| r.x == 0.0 && r.y == 0.0) {
+            (record.x, record.y) =
+                Projection::WebMercator.from_geographic(record.longitude, record.latitude);
+        }
+        Ok(Self(records))
+    }
+}
+
+impl IntoGeoJson for SpatialAddressesRaw {
+    fn to_geojson<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |a| Some((a.longitude, a.latitude)), path)
+    }
+}
+
 impl IntoCsv<SpatialAddressesRaw> for SpatialAddressesRaw {
     fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
         let records = from_csv(path)?;
@@ -48,6 +48,14 @@ pub struct GrantsPassAddress {
     /// name.
     #[serde(rename = "St_PosTyp")]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "St_PosDir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(deserialize_with = "csv::invalid_option")]
@@ -202,6 +210,14 @@ impl Address for GrantsPassAddress {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
@@ -338,6 +354,14 @@ pub struct GrantsPassSpatialAddress {
     #[serde(deserialize_with = "StreetNamePostType::deserialize_mixed")]
     #[serde(rename = "St_PosTyp")]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "St_PosDir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(deserialize_with = "csv::invalid_option")]
@@ -506,6 +530,14 @@ impl Address for GrantsPassSpatialAddress {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
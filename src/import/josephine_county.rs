@@ -60,6 +60,14 @@ pub struct JosephineCountyAddress2024 {
         rename = "st_postyp"
     )]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "st_posdir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(
@@ -158,6 +166,14 @@ impl Address for JosephineCountyAddress2024 {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
@@ -305,6 +321,14 @@ pub struct JosephineCountySpatialAddress2024 {
         rename = "st_postyp"
     )]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "st_posdir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(
@@ -415,6 +439,14 @@ impl Address for JosephineCountySpatialAddress2024 {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
@@ -566,6 +598,14 @@ pub struct JosephineCountyAddress {
         rename = "type"
     )]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "postdir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(
@@ -685,6 +725,14 @@ impl Address for JosephineCountyAddress {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
@@ -816,6 +864,14 @@ pub struct JosephineCountySpatialAddress {
         rename = "type"
     )]
     pub street_name_post_type: Option<StreetNamePostType>,
+    /// The `street_name_post_directional` field represents the street name post directional
+    /// component of the complete street name.
+    #[serde(
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed",
+        rename = "postdir"
+    )]
+    pub street_name_post_directional: Option<StreetNamePreDirectional>,
     /// The `subaddress_type` field represents the subaddress type component of the complete
     /// subaddress.
     #[serde(
@@ -949,6 +1005,14 @@ impl Address for JosephineCountySpatialAddress {
         &mut self.street_name_post_type
     }
 
+    fn street_name_post_directional(&self) -> &Option<StreetNamePreDirectional> {
+        &self.street_name_post_directional
+    }
+
+    fn street_name_post_directional_mut(&mut self) -> &mut Option<StreetNamePreDirectional> {
+        &mut self.street_name_post_directional
+    }
+
     fn subaddress_id(&self) -> &Option<String> {
         &self.subaddress_identifier
     }
@@ -0,0 +1,233 @@
+//! The `intersection` module holds street intersections, such as "6TH ST & G ST", which dispatch
+//! and inspection records use in place of an address to give a location by its cross streets.
+use crate::{
+    Address, Cartesian, Geographic, PostalCommunity, SpatialAddress, SpatialAddresses,
+    SpatialIndex, StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier,
+    StreetNamePreType, StreetSeparator,
+};
+
+/// The `CompleteStreetName` struct holds the components of a complete street name under the FGDC
+/// standard, without an address number, as read by [`crate::Parse::complete_street_name`].
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct CompleteStreetName {
+    /// The `pre_directional` field holds the street name pre directional, if any.
+    pub pre_directional: Option<StreetNamePreDirectional>,
+    /// The `pre_modifier` field holds the street name pre modifier, if any.
+    pub pre_modifier: Option<StreetNamePreModifier>,
+    /// The `pre_type` field holds the street name pre type, if any.
+    pub pre_type: Option<StreetNamePreType>,
+    /// The `separator` field holds the separator element, if any.
+    pub separator: Option<StreetSeparator>,
+    /// The `street_name` field holds the street name.
+    pub street_name: String,
+    /// The `post_type` field holds the street name post type, if any.
+    pub post_type: Option<StreetNamePostType>,
+    /// The `post_directional` field holds the street name post directional, if any.
+    pub post_directional: Option<StreetNamePreDirectional>,
+}
+
+impl CompleteStreetName {
+    /// The `label` method returns the complete street name with abbreviated directionals and post
+    /// type, e.g. "NE 7TH ST".
+    pub fn label(&self) -> String {
+        let mut words = Vec::new();
+        if let Some(directional) = &self.pre_directional {
+            words.push(directional.abbreviate());
+        }
+        if let Some(modifier) = &self.pre_modifier {
+            words.push(modifier.upper());
+        }
+        if let Some(pre_type) = &self.pre_type {
+            words.push(pre_type.upper());
+        }
+        if let Some(separator) = &self.separator {
+            words.push(separator.upper());
+        }
+        words.push(self.street_name.clone());
+        if let Some(post_type) = &self.post_type {
+            words.push(post_type.abbreviate());
+        }
+        if let Some(directional) = &self.post_directional {
+            words.push(directional.abbreviate());
+        }
+        words.join(" ")
+    }
+
+    /// The `matches` method returns `true` if `address` lies on the street.  Components missing
+    /// from the street name match any value, so "F ST" matches addresses on both "NE F ST" and
+    /// "SE F ST", while the street name and pre type compare using
+    /// [`StreetNamePreType::same_street`].
+    pub fn matches<T: Address>(&self, address: &T) -> bool {
+        let same_street = StreetNamePreType::same_street(
+            (self.pre_type, &self.street_name),
+            (*address.street_name_pre_type(), address.street_name()),
+        );
+        let agrees =
+            |component: &Option<_>, other: &Option<_>| component.is_none() || component == other;
+        same_street
+            && agrees(&self.pre_directional, address.directional())
+            && agrees(
+                &self.post_directional,
+                address.street_name_post_directional(),
+            )
+            && (self.post_type.is_none()
+                || self.pre_type.is_some_and(|pre_type| pre_type.is_route())
+                || self.post_type == *address.street_type())
+    }
+}
+
+impl std::fmt::Display for CompleteStreetName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// The `Intersection` struct holds a location given by two cross streets, as read by
+/// [`crate::Parse::intersection`].
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct Intersection {
+    /// The `first` field holds the first cross street.
+    pub first: CompleteStreetName,
+    /// The `second` field holds the second cross street.
+    pub second: CompleteStreetName,
+    /// The `postal_community` field holds the postal community following the cross streets, if
+    /// any.
+    pub postal_community: Option<PostalCommunity>,
+}
+
+impl Intersection {
+    /// The `label` method returns the cross streets joined by an ampersand, e.g.
+    /// "NE 7TH ST & NE F ST".
+    pub fn label(&self) -> String {
+        format!("{} & {}", self.first.label(), self.second.label())
+    }
+
+    /// The `resolve` method estimates the location of the intersection from the addresses in
+    /// `addresses`.  The estimate is the midpoint of the closest pair of addresses, one on each
+    /// cross street, since the addresses nearest a corner flank the intersection.  Returns `None`
+    /// if either street has no addresses, or if the streets are the same.  The
+    /// [`IntersectionPoint::separation`] of the estimate reports the distance between the pair,
+    /// and a large separation suggests that the streets do not cross.
+    pub fn resolve(&self, addresses: &SpatialAddresses) -> Option<IntersectionPoint> {
+        if self.first == self.second {
+            return None;
+        }
+        let in_community = |address: &&SpatialAddress| match &self.postal_community {
+            Some(community) => address
+                .postal_community()
+                .eq_ignore_ascii_case(&community.label()),
+            None => true,
+        };
+        let first = addresses
+            .iter()
+            .filter(in_community)
+            .filter(|address| self.first.matches(*address))
+            .collect::<Vec<&SpatialAddress>>();
+        let second = addresses
+            .iter()
+            .filter(in_community)
+            .filter(|address| self.second.matches(*address))
+            .cloned()
+            .collect::<Vec<SpatialAddress>>();
+        tracing::trace!(
+            "{} addresses on {}, {} addresses on {}.",
+            first.len(),
+            self.first,
+            second.len(),
+            self.second
+        );
+        if second.is_empty() {
+            return None;
+        }
+        let index = SpatialIndex::new(&second[..]);
+        first
+            .iter()
+            .filter_map(|address| {
+                index
+                    .nearest(address.x, address.y, 1)
+                    .first()
+                    .map(|neighbor| (*address, neighbor.address, neighbor.distance))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(a, b, separation)| IntersectionPoint {
+                latitude: (a.latitude + b.latitude) / 2.0,
+                longitude: (a.longitude + b.longitude) / 2.0,
+                x: (a.x + b.x) / 2.0,
+                y: (a.y + b.y) / 2.0,
+                separation,
+                first_address: a.label(),
+                second_address: b.label(),
+            })
+    }
+}
+
+impl std::fmt::Display for Intersection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// The `IntersectionPoint` struct holds the estimated location of an [`Intersection`], as
+/// returned by [`Intersection::resolve`].
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+pub struct IntersectionPoint {
+    /// The `latitude` field holds the estimated latitude of the intersection.
+    pub latitude: f64,
+    /// The `longitude` field holds the estimated longitude of the intersection.
+    pub longitude: f64,
+    /// The `x` field holds the estimated X coordinate of the intersection, in the projection of
+    /// the reference addresses.
+    pub x: f64,
+    /// The `y` field holds the estimated Y coordinate of the intersection, in the projection of
+    /// the reference addresses.
+    pub y: f64,
+    /// The `separation` field holds the distance between the addresses used for the estimate, in
+    /// the units of the projected coordinates.
+    pub separation: f64,
+    /// The `first_address` field holds the label of the address on the first cross street.
+    pub first_address: String,
+    /// The `second_address` field holds the label of the address on the second cross street.
+    pub second_address: String,
+}
+
+impl Cartesian for IntersectionPoint {
+    fn x(&self) -> f64 {
+        self.x
+    }
+
+    fn y(&self) -> f64 {
+        self.y
+    }
+}
+
+impl Geographic for IntersectionPoint {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
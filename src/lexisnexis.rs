@@ -1,7 +1,7 @@
 //! The `lexisnexis` module produces address range reports for the LexisNexis dispatch service.
 use crate::{
-    from_bin, from_csv, to_bin, to_csv, Address, AddressError, AddressErrorKind, Addresses,
-    Bincode, Builder, IntoBin, IntoCsv, Io,
+    from_bin, from_csv, to_bin, to_csv, to_geojson, Address, AddressError, AddressErrorKind,
+    Addresses, Bincode, Builder, IntoBin, IntoCsv, IntoGeoJson, Io,
 };
 use derive_more::{Deref, DerefMut};
 use serde::{Deserialize, Serialize};
@@ -24,8 +24,8 @@ pub struct LexisNexisItemBuilder {
     pub street_name: Option<String>,
     /// The `street_name_post_type` field represents the street name post type.
     pub street_name_post_type: Option<String>,
-    /// The `street_name_post_directional` field represents the street name post-directional.
-    /// Grants Pass does not use street name post directional designations.
+    /// The `street_name_post_directional` field represents the street name post-directional using
+    /// the standard postal abbreviation.
     pub street_name_post_directional: Option<String>,
     /// The `postal_community` field represents the city or postal community in an address.
     pub postal_community: Option<String>,
@@ -285,6 +285,9 @@ impl LexisNexis {
                     if let Some(street_type) = address.street_type() {
                         builder.street_name_post_type = Some(street_type.abbreviate());
                     }
+                    builder.street_name_post_directional = address
+                        .street_name_post_directional()
+                        .map(|dir| dir.abbreviate());
                     builder.postal_community = Some(address.postal_community().clone());
                     builder.zip_code = Some(address.zip());
                     if let Ok(built) = builder.build() {
@@ -322,6 +325,13 @@ impl IntoCsv<LexisNexis> for LexisNexis {
     }
 }
 
+/// Address ranges have no single location, so the features have null geometries.
+impl IntoGeoJson for LexisNexis {
+    fn to_geojson<P: AsRef<Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_geojson(&self.0, |_| None, path)
+    }
+}
+
 /// The `LexisNexisRangeItem` represents an address number `num`, and whether to include the number
 /// in the range selection.
 #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
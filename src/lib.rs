@@ -9,31 +9,47 @@ mod address_components;
 mod business;
 mod cli;
 mod compare;
+mod diagnostics;
 mod error;
 mod geo;
 mod import;
+mod intersection;
 mod lexisnexis;
+mod mailing;
+mod nena;
+mod nguid;
 mod parser;
+mod projection;
+mod rules;
+mod spatial;
 mod utils;
+mod validate;
 
 pub use address::{
     Address, AddressDelta, AddressDeltas, Addresses, CommonAddress, CommonAddresses,
     PartialAddress, PartialAddresses,
 };
 pub use address_components::{
-    zero_floor, AddressStatus, PostalCommunity, State, StreetNamePostType,
-    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
-    SubaddressType,
+    zero_floor, AddressNumberRange, AddressStatus, OrdinalStyle, PostalCommunity, PostalDelivery,
+    PostalDeliveryType, State, StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier,
+    StreetNamePreType, StreetSeparator, SubaddressType,
 };
 pub use business::{BusinessLicense, BusinessLicenses, BusinessMatchRecord, BusinessMatchRecords};
 pub use cli::Cli;
 pub use compare::{
-    AddressMatch, FireInspectionMatch, FireInspectionMatchRecord, FireInspectionMatchRecords,
-    FireInspectionMatches, MatchPartialRecord, MatchPartialRecords, MatchRecord, MatchRecords,
-    MatchStatus, Mismatch,
+    street_name_similarity, AddressIndex, AddressMatch, ComponentPolicy, FireInspectionMatch,
+    FireInspectionMatchRecord, FireInspectionMatchRecords, FireInspectionMatches, MatchAssignment,
+    MatchPartialRecord, MatchPartialRecords, MatchPolicy, MatchRecord, MatchRecords, MatchStatus,
+    Mismatch, ReconcileRecord, ReconcileRecords, ReconcileStatus, ReconcileSummary,
+};
+pub use diagnostics::{
+    AddressComponent, Ambiguity, Confidence, ParseDiagnostics, ParseToken, ParsedComponent,
+};
+pub use error::{AddressError, AddressErrorKind, Bincode, Builder, Csv, Io, Json, LineWidth, Nom};
+pub use geo::{
+    AddressPoint, AddressPoints, Cartesian, GeoAddress, GeoAddresses, Geographic, SpatialAddress,
+    SpatialAddresses,
 };
-pub use error::{AddressError, AddressErrorKind, Bincode, Builder, Csv, Io, Nom};
-pub use geo::{Cartesian, GeoAddress, GeoAddresses, Geographic, SpatialAddress, SpatialAddresses};
 pub use import::{
     Business, Businesses, FireInspection, FireInspectionRaw, FireInspections, GrantsPassAddress,
     GrantsPassAddresses, GrantsPassSpatialAddress, GrantsPassSpatialAddresses,
@@ -41,10 +57,19 @@ pub use import::{
     JosephineCountyAddresses2024, JosephineCountySpatialAddress, JosephineCountySpatialAddress2024,
     JosephineCountySpatialAddresses, JosephineCountySpatialAddresses2024, SpatialAddressesRaw,
 };
+pub use intersection::{CompleteStreetName, Intersection, IntersectionPoint};
 pub use lexisnexis::{
     LexisNexis, LexisNexisItem, LexisNexisItemBuilder, LexisNexisRange, LexisNexisRangeItem,
 };
+pub use mailing::{MailingLabel, MailingLabels};
+pub use nena::{NenaSiteAddressPoint, NenaSiteAddressPoints};
+pub use nguid::NguidGenerator;
 pub use parser::{deserialize_phone_number, parse_phone_number, Parse};
+pub use projection::{Point, Projection};
+pub use rules::{Rule, Rules};
+pub use spatial::{Neighbor, SpatialIndex};
 pub use utils::{
-    deserialize_arcgis_data, from_bin, from_csv, to_bin, to_csv, trace_init, IntoBin, IntoCsv,
+    deserialize_arcgis_data, from_bin, from_csv, from_geojson, to_bin, to_csv, to_geojson,
+    trace_init, IntoBin, IntoCsv, IntoGeoJson,
 };
+pub use validate::{Finding, Findings, Validate, ValidationRule, Validator};
@@ -0,0 +1,190 @@
+//! The `mailing` module formats addresses as mailing labels following USPS Publication 28,
+//! Postal Addressing Standards.
+use crate::{to_csv, Address, AddressErrorKind, IntoCsv, Io, LineWidth};
+use derive_more::{Deref, DerefMut};
+
+/// The `MailingLabel` struct holds the lines of a mailing label under USPS Publication 28.  Lines
+/// are in upper case, use standard abbreviations, omit punctuation other than the hyphen, slash
+/// and pound sign, and hold at most [`MailingLabel::LINE_WIDTH`] characters.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct MailingLabel {
+    /// The `secondary_line` field holds the secondary address unit when it does not fit on the
+    /// delivery address line.  Publication 28 places it on the line above the delivery address
+    /// line.
+    pub secondary_line: Option<String>,
+    /// The `delivery_line` field holds the delivery address line, e.g. "701 NE F ST APT 4".
+    pub delivery_line: String,
+    /// The `last_line` field holds the city, state and ZIP Code, e.g. "GRANTS PASS OR 97526".
+    pub last_line: String,
+}
+
+impl MailingLabel {
+    /// The maximum number of characters in a line of a mailing label.
+    pub const LINE_WIDTH: usize = 40;
+
+    /// The `new` method creates a mailing label for `address`.  Returns an error if the delivery
+    /// address or the last line exceeds [`MailingLabel::LINE_WIDTH`] characters.
+    pub fn new<T: Address + ?Sized>(address: &T) -> Result<Self, LineWidth> {
+        Self::with_zip4(address, None)
+    }
+
+    /// The `with_zip4` method creates a mailing label for `address`, adding the ZIP+4 add-on
+    /// code `plus_four` to the ZIP Code.  A nine-digit zip code on the address also prints in
+    /// ZIP+4 form.
+    pub fn with_zip4<T: Address + ?Sized>(
+        address: &T,
+        plus_four: Option<u16>,
+    ) -> Result<Self, LineWidth> {
+        // The pre type prints abbreviated, as in "HWY 199".
+        let street = address.complete_street_name(true);
+        let street = match address.street_name_pre_type() {
+            Some(pre_type) => street.replacen(&pre_type.upper(), &pre_type.abbreviate(), 1),
+            None => street,
+        };
+        let primary = Self::clean(&format!("{} {street}", address.complete_address_number()));
+        let mut secondary = Vec::new();
+        if let Some(building) = address.building() {
+            secondary.push(format!("BLDG {building}"));
+        }
+        match (address.subaddress_type(), address.subaddress_id()) {
+            (Some(kind), Some(id)) => secondary.push(format!("{} {id}", kind.abbreviate())),
+            (Some(kind), None) => secondary.push(kind.abbreviate()),
+            (None, Some(id)) => secondary.push(format!("# {id}")),
+            (None, None) => {}
+        }
+        let secondary = Self::clean(&secondary.join(" "));
+        let (secondary_line, delivery_line) = if secondary.is_empty() {
+            (None, primary)
+        } else if primary.len() + secondary.len() < Self::LINE_WIDTH {
+            (None, format!("{primary} {secondary}"))
+        } else {
+            (Some(secondary), primary)
+        };
+        let last_line = Self::clean(&format!(
+            "{} {} {}",
+            address.postal_community(),
+            address.state().abbreviate(),
+            Self::zip_code(address.zip(), plus_four)
+        ));
+        let label = Self {
+            secondary_line,
+            delivery_line,
+            last_line,
+        };
+        match label
+            .lines()
+            .iter()
+            .find(|line| line.len() > Self::LINE_WIDTH)
+        {
+            Some(line) => Err(LineWidth::new(
+                line.to_string(),
+                Self::LINE_WIDTH,
+                "MailingLabel".to_string(),
+                line!(),
+                file!().to_string(),
+            )),
+            None => Ok(label),
+        }
+    }
+
+    /// The `lines` method returns the lines of the label from top to bottom.
+    pub fn lines(&self) -> Vec<&str> {
+        let mut lines = Vec::new();
+        if let Some(secondary) = &self.secondary_line {
+            lines.push(secondary.as_str());
+        }
+        lines.push(self.delivery_line.as_str());
+        lines.push(self.last_line.as_str());
+        lines
+    }
+
+    /// The `zip_code` method formats the ZIP Code `zip`, adding the add-on code `plus_four` in
+    /// ZIP+4 form, e.g. "97526-1234".  A nine-digit `zip` splits into the ZIP Code and add-on code.
+    pub fn zip_code(zip: i64, plus_four: Option<u16>) -> String {
+        let (zip, plus_four) = match plus_four {
+            Some(code) => (zip, Some(i64::from(code))),
+            None if zip > 99_999 => (zip / 10_000, Some(zip % 10_000)),
+            None => (zip, None),
+        };
+        match plus_four {
+            Some(code) => format!("{zip:05}-{code:04}"),
+            None => format!("{zip:05}"),
+        }
+    }
+
+    /// The `clean` method converts `line` to upper case, drops punctuation not permitted by
+    /// Publication 28, and collapses runs of whitespace.
+    fn clean(line: &str) -> String {
+        line.to_uppercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || c.is_whitespace() || "-/#".contains(*c))
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+impl std::fmt::Display for MailingLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+/// The `MailingLabels` struct holds a vector of type [`MailingLabel`].
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    Deref,
+    DerefMut,
+    derive_new::new,
+)]
+pub struct MailingLabels(Vec<MailingLabel>);
+
+impl MailingLabels {
+    /// The `from_addresses` method creates a mailing label for each address in `addresses`,
+    /// skipping with a warning the addresses with lines too long to print.
+    pub fn from_addresses<T: Address>(addresses: &[T]) -> Self {
+        let records = addresses
+            .iter()
+            .filter_map(|address| match MailingLabel::new(address) {
+                Ok(label) => Some(label),
+                Err(e) => {
+                    tracing::warn!("Skipping {}: {e}", address.label());
+                    None
+                }
+            })
+            .collect::<Vec<MailingLabel>>();
+        Self(records)
+    }
+}
+
+impl IntoCsv<MailingLabels> for MailingLabels {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = crate::from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
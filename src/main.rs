@@ -1,16 +1,21 @@
 use clap::Parser;
 use destination::{
-    trace_init, Addresses, BusinessLicenses, BusinessMatchRecords, Cartesian, Cli, CommonAddresses,
-    GeoAddresses, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin, IntoCsv,
-    JosephineCountyAddresses, JosephineCountyAddresses2024, JosephineCountySpatialAddresses2024,
-    LexisNexis, MatchPartialRecords, MatchRecords, SpatialAddress, SpatialAddresses,
-    SpatialAddressesRaw,
+    to_csv, trace_init, Addresses, BusinessLicenses, BusinessMatchRecords, Cli, CommonAddresses,
+    Findings, GeoAddresses, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin, IntoCsv,
+    IntoGeoJson, JosephineCountyAddresses, JosephineCountyAddresses2024,
+    JosephineCountySpatialAddresses2024, LexisNexis, MailingLabels, MatchPartialRecords,
+    MatchPolicy, MatchRecords, NenaSiteAddressPoints, NguidGenerator, ReconcileRecords, Rules,
+    SpatialAddresses, SpatialAddressesRaw, ValidationRule, Validator,
 };
 use tracing::{error, info, trace, warn};
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     trace_init();
+    let rules = match &cli.rules {
+        Some(path) => Rules::from_path(path)?,
+        None => Rules::default_rules().clone(),
+    };
 
     match cli.command.as_str() {
         "filter" => {
@@ -23,7 +28,11 @@ fn main() -> anyhow::Result<()> {
                             info!("Source records read: {} entries.", match_records.len());
                             let mut filtered = match_records.filter(&filter);
                             info!("Records remaining: {} entries.", filtered.len());
-                            filtered.to_csv(cli.output)?;
+                            if is_geojson(&cli.output) {
+                                filtered.to_geojson(&cli.output)?;
+                            } else {
+                                filtered.to_csv(cli.output)?;
+                            }
                         }
                         "partial" => {
                             let match_records = MatchPartialRecords::from_csv(cli.source.clone())?;
@@ -37,7 +46,11 @@ fn main() -> anyhow::Result<()> {
                             info!("Source records read: {} entries.", match_records.len());
                             let mut filtered = match_records.clone().filter(&filter);
                             info!("Records remaining: {} entries.", filtered.len());
-                            filtered.to_csv(cli.output)?;
+                            if is_geojson(&cli.output) {
+                                filtered.to_geojson(&cli.output)?;
+                            } else {
+                                filtered.to_csv(cli.output)?;
+                            }
                         }
                         _ => warn!("Unrecognized source type: {source}"),
                     }
@@ -51,19 +64,7 @@ fn main() -> anyhow::Result<()> {
             trace!("Reading source addresses.");
             let mut source_addresses = SpatialAddresses::default();
             if let Some(source_type) = &cli.source_type {
-                match source_type.as_str() {
-                    "grants_pass" => {
-                        source_addresses = SpatialAddresses::from(
-                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
-                        )
-                    }
-                    "josephine_county" => {
-                        source_addresses = SpatialAddresses::from(
-                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
-                        )
-                    }
-                    _ => error!("Invalid source data type."),
-                }
+                source_addresses = load_geo_addresses(source_type, &cli.source, &rules)?;
             } else {
                 error!("No source data type provided.");
             }
@@ -72,19 +73,7 @@ fn main() -> anyhow::Result<()> {
             let mut target_addresses = SpatialAddresses::default();
             if let Some(target) = &cli.target {
                 if let Some(target_type) = &cli.target_type {
-                    match target_type.as_str() {
-                        "grants_pass" => {
-                            target_addresses = SpatialAddresses::from(
-                                &GrantsPassSpatialAddresses::from_csv(target)?[..],
-                            )
-                        }
-                        "josephine_county" => {
-                            target_addresses = SpatialAddresses::from(
-                                &JosephineCountySpatialAddresses2024::from_csv(target)?[..],
-                            )
-                        }
-                        _ => error!("Invalid target data type."),
-                    }
+                    target_addresses = load_geo_addresses(target_type, target, &rules)?;
                 } else {
                     error!("No target data type provided.");
                 }
@@ -92,9 +81,16 @@ fn main() -> anyhow::Result<()> {
                 error!("No target data specified.");
             }
 
-            let mut deltas =
-                <SpatialAddress as Cartesian>::deltas(&source_addresses, &target_addresses, 99.0);
-            deltas.to_csv(cli.output.clone())?;
+            let policy = MatchPolicy::default().with_drift(cli.drift.unwrap_or(30.0));
+            let mut match_records =
+                MatchRecords::compare(&source_addresses, &target_addresses, &policy)
+                    .filter("drift");
+            info!("{:?} records drifted.", match_records.len());
+            if is_geojson(&cli.output) {
+                match_records.to_geojson(&cli.output)?;
+            } else {
+                match_records.to_csv(cli.output.clone())?;
+            }
         }
         "lexisnexis" => {
             info!("Reading source records.");
@@ -116,6 +112,11 @@ fn main() -> anyhow::Result<()> {
                             cli.source.clone(),
                         )?)
                     }
+                    "geojson" => {
+                        source_addresses = CommonAddresses::from(SpatialAddressesRaw::from_geojson(
+                            cli.source.clone(),
+                        )?)
+                    }
                     _ => error!("Unrecognized file format."),
                 }
             }
@@ -140,6 +141,10 @@ fn main() -> anyhow::Result<()> {
                             target_addresses =
                                 CommonAddresses::from(SpatialAddressesRaw::from_csv(target)?)
                         }
+                        "geojson" => {
+                            target_addresses =
+                                CommonAddresses::from(SpatialAddressesRaw::from_geojson(target)?)
+                        }
                         _ => error!("Invalid target data type."),
                     }
                 } else {
@@ -153,27 +158,18 @@ fn main() -> anyhow::Result<()> {
                 target_addresses.len()
             );
             let mut lx = LexisNexis::from_addresses(&source_addresses, &target_addresses)?;
-            lx.to_csv(cli.output)?;
+            if is_geojson(&cli.output) {
+                lx.to_geojson(&cli.output)?;
+            } else {
+                lx.to_csv(cli.output)?;
+            }
         }
         "save" => {
             info!("Loading and saving addresses...");
             trace!("Reading source addresses.");
             let mut source_addresses = SpatialAddresses::default();
             if let Some(source_type) = &cli.source_type {
-                match source_type.as_str() {
-                    "grants_pass" => {
-                        source_addresses = SpatialAddresses::from(
-                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
-                        )
-                    }
-                    "josephine_county" => {
-                        source_addresses = SpatialAddresses::from(
-                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
-                        );
-                        source_addresses.standardize();
-                    }
-                    _ => error!("Invalid source data type."),
-                }
+                source_addresses = load_geo_addresses(source_type, &cli.source, &rules)?;
             } else {
                 error!("No source data type provided.");
             }
@@ -306,59 +302,35 @@ fn main() -> anyhow::Result<()> {
                 );
                 info!("{:?} records categorized.", match_records.len());
                 info!("Output file: {:?}", cli.output);
-                match_records.to_csv(cli.output)?;
+                if is_geojson(&cli.output) {
+                    match_records.to_geojson(&cli.output)?;
+                } else {
+                    match_records.to_csv(cli.output)?;
+                }
             } else {
                 info!("Comparing records.");
                 let mut match_records =
                     BusinessMatchRecords::compare(&source_addresses, &target_addresses);
                 info!("{:?} records categorized.", match_records.len());
                 info!("Output file: {:?}", cli.output);
-                match_records.to_csv(cli.output)?;
+                if is_geojson(&cli.output) {
+                    match_records.to_geojson(&cli.output)?;
+                } else {
+                    match_records.to_csv(cli.output)?;
+                }
             }
         }
         "compare" => {
             info!("Reading source records.");
-            let mut source = GeoAddresses::default();
+            let mut source = SpatialAddresses::default();
             if let Some(source_type) = &cli.source_type {
-                match source_type.as_str() {
-                    "grants_pass" => {
-                        source = GeoAddresses::from(
-                            &GrantsPassSpatialAddresses::from_csv(cli.source.clone())?[..],
-                        )
-                    }
-                    "josephine_county" => {
-                        source = GeoAddresses::from(
-                            &JosephineCountySpatialAddresses2024::from_csv(cli.source.clone())?[..],
-                        )
-                    }
-                    "common" => {
-                        source =
-                            GeoAddresses::from(SpatialAddressesRaw::from_csv(cli.source.clone())?)
-                    }
-                    _ => error!("Unrecognized file format."),
-                }
+                source = load_geo_addresses(source_type, &cli.source, &rules)?;
             }
             info!("Reading target records.");
-            let mut target = GeoAddresses::default();
+            let mut target = SpatialAddresses::default();
             if let Some(target_type) = &cli.target_type {
                 if let Some(target_path) = &cli.target {
-                    match target_type.as_str() {
-                        "grants_pass" => {
-                            target = GeoAddresses::from(
-                                &GrantsPassSpatialAddresses::from_csv(target_path)?[..],
-                            )
-                        }
-                        "josephine_county" => {
-                            target = GeoAddresses::from(
-                                &JosephineCountySpatialAddresses2024::from_csv(target_path)?[..],
-                            );
-                            target.standardize();
-                        }
-                        "common" => {
-                            target = GeoAddresses::from(SpatialAddressesRaw::from_csv(target_path)?)
-                        }
-                        _ => error!("Unrecognized file format."),
-                    }
+                    target = load_geo_addresses(target_type, target_path, &rules)?;
                 }
             }
             info!("Comparing records.");
@@ -369,13 +341,255 @@ fn main() -> anyhow::Result<()> {
             // source = GeoAddresses::from(&source.filter("active")[..]);
             info!("Source records post: {}", source.len());
 
-            let mut match_records = MatchRecords::compare(&source, &target);
-            info!("{:?} records categorized.", match_records.len());
+            let mut policy = match &cli.policy {
+                Some(policy) => MatchPolicy::from_name_or_path(policy)?,
+                None => MatchPolicy::default(),
+            };
+            if let Some(max_drift) = cli.drift {
+                policy = policy.with_drift(max_drift);
+            }
+            if cli.assign {
+                if cli.threshold.is_some() {
+                    anyhow::bail!("The --threshold and --assign options cannot be combined.");
+                }
+                info!("Assigning matches one-to-one.");
+                let mut assignment = MatchRecords::assign(&source, &target, &policy);
+                info!("{:?} records categorized.", assignment.records.len());
+                let stem = cli
+                    .output
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let extension = cli
+                    .output
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let unassigned_path = cli
+                    .output
+                    .with_file_name(format!("{stem}_unassigned.{extension}"));
+                info!("Output file: {:?}", cli.output);
+                info!("Unassigned file: {:?}", unassigned_path);
+                if is_geojson(&cli.output) {
+                    assignment.records.to_geojson(&cli.output)?;
+                    assignment.unassigned.to_geojson(&unassigned_path)?;
+                } else {
+                    assignment.records.to_csv(&cli.output)?;
+                    assignment.unassigned.to_csv(&unassigned_path)?;
+                }
+            } else {
+                let source = GeoAddresses::from(&source[..]);
+                let target = GeoAddresses::from(&target[..]);
+                let mut match_records = match cli.threshold {
+                    Some(threshold) => {
+                        info!("Fuzzy matching street names at threshold {threshold}.");
+                        MatchRecords::compare_fuzzy(&source, &target, threshold, &policy)
+                    }
+                    None => MatchRecords::compare(&source, &target, &policy),
+                };
+                info!("{:?} records categorized.", match_records.len());
+                info!("Output file: {:?}", cli.output);
+                if is_geojson(&cli.output) {
+                    match_records.to_geojson(&cli.output)?;
+                } else {
+                    match_records.to_csv(cli.output)?;
+                }
+            }
+        }
+        "geocode" => {
+            info!("Reading reference records.");
+            let mut target = GeoAddresses::default();
+            if let Some(target_type) = &cli.target_type {
+                if let Some(target_path) = &cli.target {
+                    target = GeoAddresses::from(
+                        &load_geo_addresses(target_type, target_path, &rules)?[..],
+                    );
+                } else {
+                    warn!("Target parameter (-t or --target) must be set.");
+                }
+            } else {
+                warn!("Target type parameter (-z or --target-type) must be set.");
+            }
+            info!("Reference records read: {} entries.", target.len());
+            info!("Geocoding column {} of {:?}.", cli.column, cli.source);
+            target.geocode_csv(&cli.source, &cli.column, &cli.output)?;
+            info!("Output file: {:?}", cli.output);
+        }
+        "nearest" => {
+            info!("Reading reference records.");
+            let mut target = SpatialAddresses::default();
+            if let Some(target_type) = &cli.target_type {
+                if let Some(target_path) = &cli.target {
+                    target = load_geo_addresses(target_type, target_path, &rules)?;
+                } else {
+                    warn!("Target parameter (-t or --target) must be set.");
+                }
+            } else {
+                warn!("Target type parameter (-z or --target-type) must be set.");
+            }
+            info!("Reference records read: {} entries.", target.len());
+            info!("Finding nearest addresses to points in {:?}.", cli.source);
+            target.nearest_csv(
+                &cli.source,
+                &cli.x_column,
+                &cli.y_column,
+                cli.projection,
+                cli.neighbors,
+                cli.radius,
+                &cli.output,
+            )?;
+            info!("Output file: {:?}", cli.output);
+        }
+        "validate" => {
+            let mut validator = Validator::new(cli.temporary_days);
+            if let Some(filter) = &cli.filter {
+                match ValidationRule::match_mixed(filter) {
+                    Some(rule) => validator.rules = vec![rule],
+                    None => anyhow::bail!("Unrecognized validation rule: {filter}"),
+                }
+            }
+            info!("Reading source records.");
+            let mut findings = Findings::default();
+            if let Some(source_type) = &cli.source_type {
+                // Validate city and county records as read, keeping the Global ID and created date
+                // of city records and leaving county records unstandardized.
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        let records = GrantsPassSpatialAddresses::from_csv(&cli.source)?;
+                        info!("Source records read: {} entries.", records.len());
+                        findings = validator.validate(&records[..]);
+                    }
+                    "josephine_county" => {
+                        let records = JosephineCountySpatialAddresses2024::from_csv(&cli.source)?;
+                        info!("Source records read: {} entries.", records.len());
+                        findings = validator.validate(&records[..]);
+                    }
+                    _ => {
+                        let records = load_geo_addresses(source_type, &cli.source, &rules)?;
+                        info!("Source records read: {} entries.", records.len());
+                        findings = validator.validate(&records[..]);
+                    }
+                }
+            } else {
+                warn!("Source type parameter (-k or --source-type) must be set.");
+            }
+            info!("Findings: {} entries.", findings.len());
+            findings.to_csv(&cli.output)?;
+            info!("Output file: {:?}", cli.output);
+        }
+        "mailing" => {
+            info!("Reading source records.");
+            let mut labels = MailingLabels::default();
+            if let Some(source_type) = &cli.source_type {
+                let records = load_geo_addresses(source_type, &cli.source, &rules)?;
+                info!("Source records read: {} entries.", records.len());
+                labels = MailingLabels::from_addresses(&records[..]);
+            } else {
+                warn!("Source type parameter (-k or --source-type) must be set.");
+            }
+            info!("Mailing labels: {} entries.", labels.len());
+            labels.to_csv(&cli.output)?;
+            info!("Output file: {:?}", cli.output);
+        }
+        "reconcile" => {
+            info!("Reading source records.");
+            let mut source = SpatialAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                source = load_geo_addresses(source_type, &cli.source, &rules)?;
+            }
+            info!("Reading target records.");
+            let mut target = SpatialAddresses::default();
+            if let Some(target_type) = &cli.target_type {
+                if let Some(target_path) = &cli.target {
+                    target = load_geo_addresses(target_type, target_path, &rules)?;
+                }
+            }
+            let mut policy = match &cli.policy {
+                Some(policy) => MatchPolicy::from_name_or_path(policy)?,
+                None => MatchPolicy::default(),
+            };
+            if let Some(max_drift) = cli.drift {
+                policy = policy.with_drift(max_drift);
+            }
+            info!("Reconciling records.");
+            let mut records = ReconcileRecords::reconcile(&source, &target, &policy);
+            let summary = records.summary();
+            info!("Reconciliation summary:\n{summary}");
+            if let Some(filter) = &cli.filter {
+                records = records.filter(filter);
+            }
+            info!("Output file: {:?}", cli.output);
+            if is_geojson(&cli.output) {
+                records.to_geojson(&cli.output)?;
+            } else {
+                records.to_csv(&cli.output)?;
+            }
+            let stem = cli
+                .output
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let summary_path = cli.output.with_file_name(format!("{stem}_summary.csv"));
+            to_csv(&mut [summary], summary_path.clone())?;
+            info!("Summary file: {:?}", summary_path);
+        }
+        "nena" => {
+            let generator = NguidGenerator::new(&cli.agency);
+            info!("Reading source records.");
+            let mut points = NenaSiteAddressPoints::default();
+            if let Some(source_type) = &cli.source_type {
+                // City records carry the Global IDs that seed the NGUIDs.
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        let records = GrantsPassSpatialAddresses::from_csv(&cli.source)?;
+                        info!("Source records read: {} entries.", records.len());
+                        points = NenaSiteAddressPoints::with_nguid(&records[..], &generator);
+                    }
+                    _ => {
+                        let records = load_geo_addresses(source_type, &cli.source, &rules)?;
+                        info!("Source records read: {} entries.", records.len());
+                        points = NenaSiteAddressPoints::with_nguid(&records[..], &generator);
+                    }
+                }
+            } else {
+                warn!("Source type parameter (-k or --source-type) must be set.");
+            }
+            info!("Site/structure address points: {} entries.", points.len());
+            points.to_csv(&cli.output)?;
             info!("Output file: {:?}", cli.output);
-            match_records.to_csv(cli.output)?;
         }
         _ => {}
     }
 
     Ok(())
 }
+
+/// The `load_geo_addresses` function reads the addresses at `path` in the format named by `kind`,
+/// one of "grants_pass", "josephine_county", "common" or "geojson".  County addresses are
+/// standardized using the rule set `rules`.
+fn load_geo_addresses(
+    kind: &str,
+    path: &std::path::Path,
+    rules: &Rules,
+) -> anyhow::Result<SpatialAddresses> {
+    let addresses = match kind {
+        "grants_pass" => SpatialAddresses::from(&GrantsPassSpatialAddresses::from_csv(path)?[..]),
+        "josephine_county" => {
+            let mut addresses =
+                SpatialAddresses::from(&JosephineCountySpatialAddresses2024::from_csv(path)?[..]);
+            addresses.standardize_with(rules);
+            addresses
+        }
+        "common" => SpatialAddresses::from(SpatialAddressesRaw::from_csv(path)?),
+        "geojson" => SpatialAddresses::from(SpatialAddressesRaw::from_geojson(path)?),
+        _ => anyhow::bail!("Unrecognized file format: {kind}"),
+    };
+    Ok(addresses)
+}
+
+/// The `is_geojson` function returns `true` if the output path at `path` has a `geojson`
+/// extension, indicating output to GeoJSON instead of CSV.
+fn is_geojson(path: &std::path::Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("geojson"))
+}
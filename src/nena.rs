@@ -0,0 +1,289 @@
+//! The `nena` module exports address points to the Site/Structure Address Point (SSAP) layer of
+//! the NENA NG9-1-1 GIS Data Model (NENA-STA-006), used by NG9-1-1 call routing and location
+//! validation.
+use crate::{
+    to_csv, Address, AddressErrorKind, GrantsPassSpatialAddress, IntoCsv, Io, NguidGenerator,
+    PostalCommunity, SpatialAddress, Validate,
+};
+use derive_more::{Deref, DerefMut};
+
+/// The `NenaSiteAddressPoint` struct holds a site/structure address point with the fields of the
+/// NENA NG9-1-1 SSAP layer, in the order of the standard.  Fields without a value in the source
+/// data are `None`, and write as empty values.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+pub struct NenaSiteAddressPoint {
+    /// The `discrepancy_agency_id` field holds the domain of the agency responsible for
+    /// discrepancy reports.
+    #[serde(rename = "DiscrpAgID")]
+    pub discrepancy_agency_id: Option<String>,
+    /// The `date_updated` field holds the date and time of the last update to the feature.
+    #[serde(rename = "DateUpdate")]
+    pub date_updated: Option<String>,
+    /// The `effective` field holds the date and time the feature takes effect.
+    #[serde(rename = "Effective")]
+    pub effective: Option<String>,
+    /// The `expire` field holds the date and time the feature expires.
+    #[serde(rename = "Expire")]
+    pub expire: Option<String>,
+    /// The `nguid` field holds the NENA globally unique identifier of the feature.
+    #[serde(rename = "Site_NGUID")]
+    pub nguid: Option<String>,
+    /// The `country` field holds the two-letter ISO 3166-1 country code.
+    #[serde(rename = "Country")]
+    pub country: String,
+    /// The `state` field holds the two-letter state abbreviation.
+    #[serde(rename = "State")]
+    pub state: String,
+    /// The `county` field holds the name of the county.
+    #[serde(rename = "County")]
+    pub county: Option<String>,
+    /// The `additional_code` field holds a code identifying a geographic area not otherwise
+    /// described.
+    #[serde(rename = "AddCode")]
+    pub additional_code: Option<String>,
+    /// The `additional_data_uri` field holds the URI of additional data about the site.
+    #[serde(rename = "AddDataURI")]
+    pub additional_data_uri: Option<String>,
+    /// The `incorporated_municipality` field holds the name of the incorporated municipality.
+    #[serde(rename = "Inc_Muni")]
+    pub incorporated_municipality: Option<String>,
+    /// The `unincorporated_community` field holds the name of the unincorporated community.
+    #[serde(rename = "Uninc_Comm")]
+    pub unincorporated_community: Option<String>,
+    /// The `neighborhood_community` field holds the name of the neighborhood.
+    #[serde(rename = "Nbrhd_Comm")]
+    pub neighborhood_community: Option<String>,
+    /// The `address_number_prefix` field holds the address number prefix.
+    #[serde(rename = "AddNum_Pre")]
+    pub address_number_prefix: Option<String>,
+    /// The `address_number` field holds the address number.
+    #[serde(rename = "AddNum")]
+    pub address_number: i64,
+    /// The `address_number_suffix` field holds the address number suffix.
+    #[serde(rename = "AddNum_Suf")]
+    pub address_number_suffix: Option<String>,
+    /// The `street_name_pre_modifier` field holds the street name pre modifier.
+    #[serde(rename = "St_PreMod")]
+    pub street_name_pre_modifier: Option<String>,
+    /// The `street_name_pre_directional` field holds the street name pre directional, spelled
+    /// out.
+    #[serde(rename = "St_PreDir")]
+    pub street_name_pre_directional: Option<String>,
+    /// The `street_name_pre_type` field holds the street name pre type, spelled out.
+    #[serde(rename = "St_PreTyp")]
+    pub street_name_pre_type: Option<String>,
+    /// The `street_name_pre_type_separator` field holds the separator element.
+    #[serde(rename = "St_PreSep")]
+    pub street_name_pre_type_separator: Option<String>,
+    /// The `street_name` field holds the street name.
+    #[serde(rename = "St_Name")]
+    pub street_name: String,
+    /// The `street_name_post_type` field holds the street name post type, spelled out.
+    #[serde(rename = "St_PosTyp")]
+    pub street_name_post_type: Option<String>,
+    /// The `street_name_post_directional` field holds the street name post directional, spelled
+    /// out.
+    #[serde(rename = "St_PosDir")]
+    pub street_name_post_directional: Option<String>,
+    /// The `street_name_post_modifier` field holds the street name post modifier.
+    #[serde(rename = "St_PosMod")]
+    pub street_name_post_modifier: Option<String>,
+    /// The `legacy_street_name_pre_directional` field holds the pre directional of the legacy
+    /// street name.
+    #[serde(rename = "LSt_PreDir")]
+    pub legacy_street_name_pre_directional: Option<String>,
+    /// The `legacy_street_name` field holds the legacy street name.
+    #[serde(rename = "LSt_Name")]
+    pub legacy_street_name: Option<String>,
+    /// The `legacy_street_name_type` field holds the type of the legacy street name.
+    #[serde(rename = "LSt_Type")]
+    pub legacy_street_name_type: Option<String>,
+    /// The `legacy_street_name_post_directional` field holds the post directional of the legacy
+    /// street name.
+    #[serde(rename = "LSt_PosDir")]
+    pub legacy_street_name_post_directional: Option<String>,
+    /// The `emergency_service_number` field holds the emergency service number of the site.
+    #[serde(rename = "ESN")]
+    pub emergency_service_number: Option<String>,
+    /// The `msag_community` field holds the community name of the site in the Master Street
+    /// Address Guide.
+    #[serde(rename = "MSAGComm")]
+    pub msag_community: Option<String>,
+    /// The `postal_community` field holds the postal community name.
+    #[serde(rename = "Post_Comm")]
+    pub postal_community: String,
+    /// The `postal_code` field holds the zip code.
+    #[serde(rename = "Post_Code")]
+    pub postal_code: String,
+    /// The `postal_code_4` field holds the zip+4 extension.
+    #[serde(rename = "Post_Code4")]
+    pub postal_code_4: Option<String>,
+    /// The `building` field holds the building identifier.
+    #[serde(rename = "Building")]
+    pub building: Option<String>,
+    /// The `floor` field holds the floor identifier.
+    #[serde(rename = "Floor")]
+    pub floor: Option<String>,
+    /// The `unit` field holds the unit type and identifier, e.g. "APARTMENT 4".
+    #[serde(rename = "Unit")]
+    pub unit: Option<String>,
+    /// The `room` field holds the room identifier.
+    #[serde(rename = "Room")]
+    pub room: Option<String>,
+    /// The `seat` field holds the seat or desk identifier.
+    #[serde(rename = "Seat")]
+    pub seat: Option<String>,
+    /// The `additional_location` field holds additional location information.
+    #[serde(rename = "Addtl_Loc")]
+    pub additional_location: Option<String>,
+    /// The `landmark_name` field holds the name of a landmark at the site.
+    #[serde(rename = "LandmkName")]
+    pub landmark_name: Option<String>,
+    /// The `mile_post` field holds the mile post of the site.
+    #[serde(rename = "Mile_Post")]
+    pub mile_post: Option<String>,
+    /// The `place_type` field holds the type of place at the site.
+    #[serde(rename = "Place_Type")]
+    pub place_type: Option<String>,
+    /// The `placement` field holds the method used to place the point.
+    #[serde(rename = "Placement")]
+    pub placement: Option<String>,
+    /// The `longitude` field holds the longitude of the point, in decimal degrees.
+    #[serde(rename = "Longitude")]
+    pub longitude: f64,
+    /// The `latitude` field holds the latitude of the point, in decimal degrees.
+    #[serde(rename = "Latitude")]
+    pub latitude: f64,
+    /// The `elevation` field holds the elevation of the point.
+    #[serde(rename = "Elevation")]
+    pub elevation: Option<f64>,
+}
+
+impl NenaSiteAddressPoint {
+    /// The `from_address` method creates an address point from the components of `address` at
+    /// the geographic coordinates `longitude` and `latitude`.
+    pub fn from_address<T: Address>(address: &T, longitude: f64, latitude: f64) -> Self {
+        let community = PostalCommunity::match_mixed(address.postal_community());
+        let unit = match (address.subaddress_type(), address.subaddress_id()) {
+            (Some(kind), Some(id)) => Some(format!("{} {id}", kind.upper())),
+            (Some(kind), None) => Some(kind.upper()),
+            (None, Some(id)) => Some(id.clone()),
+            (None, None) => None,
+        };
+        Self {
+            country: "US".to_string(),
+            state: address.state().abbreviate(),
+            county: community.map(|community| Self::county(&community).to_string()),
+            address_number: address.number(),
+            address_number_suffix: address.number_suffix().clone(),
+            street_name_pre_modifier: address
+                .street_name_pre_modifier()
+                .as_ref()
+                .map(|v| v.upper()),
+            street_name_pre_directional: address.directional().as_ref().map(|v| v.to_string()),
+            street_name_pre_type: address.street_name_pre_type().as_ref().map(|v| v.upper()),
+            street_name_pre_type_separator: address
+                .street_name_separator()
+                .as_ref()
+                .map(|v| v.upper()),
+            street_name: address.street_name().clone(),
+            street_name_post_type: address.street_type().as_ref().map(|v| v.to_string()),
+            street_name_post_directional: address
+                .street_name_post_directional()
+                .as_ref()
+                .map(|v| v.to_string()),
+            postal_community: address.postal_community().clone(),
+            postal_code: address.zip().to_string(),
+            building: address.building().clone(),
+            floor: address.floor().as_ref().map(|v| v.to_string()),
+            unit,
+            longitude,
+            latitude,
+            ..Default::default()
+        }
+    }
+
+    /// The `county` method returns the name of the county of the postal community `community`.
+    fn county(community: &PostalCommunity) -> &'static str {
+        match community {
+            PostalCommunity::GrantsPass | PostalCommunity::Merlin => "JOSEPHINE",
+            PostalCommunity::Medford => "JACKSON",
+        }
+    }
+}
+
+impl From<&SpatialAddress> for NenaSiteAddressPoint {
+    fn from(address: &SpatialAddress) -> Self {
+        Self::from_address(address, address.longitude, address.latitude)
+    }
+}
+
+impl From<&GrantsPassSpatialAddress> for NenaSiteAddressPoint {
+    fn from(address: &GrantsPassSpatialAddress) -> Self {
+        Self {
+            date_updated: Some(address.last_edited_date.clone()).filter(|date| !date.is_empty()),
+            incorporated_municipality: address.incorporated_municipality.clone(),
+            unincorporated_community: address.unincorporated_community.clone(),
+            ..Self::from_address(address, address.longitude, address.latitude)
+        }
+    }
+}
+
+/// The `NenaSiteAddressPoints` struct holds a vector of type [`NenaSiteAddressPoint`].
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+    Deref,
+    DerefMut,
+    derive_new::new,
+)]
+pub struct NenaSiteAddressPoints(Vec<NenaSiteAddressPoint>);
+
+impl NenaSiteAddressPoints {
+    /// The `with_nguid` method converts `addresses` to address points, assigning each point the
+    /// NGUID of its source address from `generator`, and the agency of `generator` as the
+    /// discrepancy agency.
+    pub fn with_nguid<'a, T: Validate>(addresses: &'a [T], generator: &NguidGenerator) -> Self
+    where
+        NenaSiteAddressPoint: From<&'a T>,
+    {
+        let records = addresses
+            .iter()
+            .map(|address| NenaSiteAddressPoint {
+                discrepancy_agency_id: Some(generator.agency.clone()),
+                nguid: Some(generator.address_nguid(address)),
+                ..NenaSiteAddressPoint::from(address)
+            })
+            .collect::<Vec<NenaSiteAddressPoint>>();
+        Self(records)
+    }
+}
+
+impl<'a, T> From<&'a [T]> for NenaSiteAddressPoints
+where
+    NenaSiteAddressPoint: From<&'a T>,
+{
+    fn from(addresses: &'a [T]) -> Self {
+        let records = addresses
+            .iter()
+            .map(NenaSiteAddressPoint::from)
+            .collect::<Vec<NenaSiteAddressPoint>>();
+        Self(records)
+    }
+}
+
+impl IntoCsv<NenaSiteAddressPoints> for NenaSiteAddressPoints {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = crate::from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
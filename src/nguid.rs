@@ -0,0 +1,90 @@
+//! The `nguid` module mints NENA globally unique identifiers (NGUIDs) for features exported to
+//! the NG9-1-1 GIS Data Model.  An NGUID takes the form
+//! `urn:emergency:uid:gis:{layer}:{local id}:{agency domain}`, and must not change between
+//! exports of the same feature.
+use crate::validate::normalize_global_id;
+use crate::{Address, Validate};
+
+/// The `NAMESPACE` constant is the UUID namespace of the name-based (version 5) UUIDs derived
+/// from addresses.
+const NAMESPACE: uuid::Uuid = uuid::Uuid::from_u128(0x61eb_018b_0ce0_4edf_bdf8_4ba7_69a2_73ba);
+
+/// The `NguidGenerator` struct mints NGUIDs for the features of a single layer, on behalf of the
+/// agency identified by `agency`.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct NguidGenerator {
+    /// The `agency` field holds the domain name of the agency responsible for the features, e.g.
+    /// "grantspassoregon.gov".
+    pub agency: String,
+    /// The `layer` field holds the layer prefix of the features, e.g. "SSAP" for site/structure
+    /// address points.
+    pub layer: String,
+}
+
+impl NguidGenerator {
+    /// The `new` method creates a generator for site/structure address points of the agency with
+    /// domain name `agency`.
+    pub fn new(agency: &str) -> Self {
+        Self {
+            agency: agency.trim().to_lowercase(),
+            layer: "SSAP".to_string(),
+        }
+    }
+
+    /// The `nguid` method returns the NGUID for the feature with local id `local_id`.
+    pub fn nguid(&self, local_id: &str) -> String {
+        format!(
+            "urn:emergency:uid:gis:{}:{}:{}",
+            self.layer, local_id, self.agency
+        )
+    }
+
+    /// The `address_nguid` method returns the NGUID of `address`, using the local id from
+    /// [`NguidGenerator::local_id`].
+    pub fn address_nguid<T: Validate>(&self, address: &T) -> String {
+        self.nguid(&Self::local_id(address))
+    }
+
+    /// The `local_id` method returns the local id of `address`.  The local id is the ESRI Global
+    /// ID of the record in upper case without braces, so the id survives edits to the address.
+    /// Records without a Global ID fall back to the [`NguidGenerator::stable_id`] of the address.
+    pub fn local_id<T: Validate>(address: &T) -> String {
+        match address.global_id().and_then(normalize_global_id) {
+            Some(id) => id,
+            None => Self::stable_id(address)
+                .hyphenated()
+                .to_string()
+                .to_uppercase(),
+        }
+    }
+
+    /// The `record_id` method returns the local id of `address` from [`NguidGenerator::local_id`]
+    /// as a UUID.  A Global ID that does not read as a UUID derives a name-based UUID, so every
+    /// record with a Global ID keeps its id through edits to the address.
+    pub fn record_id<T: Validate>(address: &T) -> uuid::Uuid {
+        let id = Self::local_id(address);
+        uuid::Uuid::parse_str(&id).unwrap_or_else(|_| uuid::Uuid::new_v5(&NAMESPACE, id.as_bytes()))
+    }
+
+    /// The `stable_id` method derives a name-based (version 5) UUID from the label and zip code
+    /// of `address`.  The same address produces the same id on every run, so records keyed by the
+    /// id join to prior outputs, but the id changes if the address changes.
+    pub fn stable_id<T: Address + ?Sized>(address: &T) -> uuid::Uuid {
+        let key = format!("{}|{}", address.label(), address.zip());
+        uuid::Uuid::new_v5(&NAMESPACE, key.as_bytes())
+    }
+
+    /// The `parse` method splits `nguid` into its layer prefix, local id and agency domain, or
+    /// returns `None` if `nguid` is not a GIS NGUID.
+    pub fn parse(nguid: &str) -> Option<(&str, &str, &str)> {
+        let rest = nguid.strip_prefix("urn:emergency:uid:gis:")?;
+        let (layer, rest) = rest.split_once(':')?;
+        let (local_id, agency) = rest.rsplit_once(':')?;
+        if layer.is_empty() || local_id.is_empty() || agency.is_empty() {
+            return None;
+        }
+        Some((layer, local_id, agency))
+    }
+}
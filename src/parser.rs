@@ -1,9 +1,11 @@
 //! The `parser` module contains functions for parsing unstructured text into address components.
 use crate::{
-    PartialAddress, PostalCommunity, State, StreetNamePostType, StreetNamePreDirectional,
-    StreetNamePreModifier, StreetNamePreType, StreetSeparator, SubaddressType,
+    AddressComponent, CompleteStreetName, Intersection, OrdinalStyle, PartialAddress,
+    PostalCommunity, PostalDelivery, PostalDeliveryType, State, StreetNamePostType,
+    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
+    SubaddressType,
 };
-use nom::bytes::complete::{tag, take_until};
+use nom::bytes::complete::{tag, tag_no_case, take_until};
 use nom::character::complete;
 use nom::{branch, combinator, AsChar, IResult, Parser};
 use serde::de::{Deserialize, Deserializer};
@@ -14,7 +16,8 @@ pub struct Parse;
 
 impl Parse {
     /// The `address_number` function expects one or more numeric digits, returned as an i64 value.
-    /// TODO: 2501-2503 address range should read as 2501 and discard remainder of range.
+    /// For an address range like "2501-2503", reads the lower bound, see
+    /// [`Parse::address_number_range`] for the upper bound.
     pub fn address_number(input: &str) -> IResult<&str, Option<i64>> {
         // Strip preceding whitespace
         let (remaining, _) = complete::space0(input)?;
@@ -30,17 +33,72 @@ impl Parse {
         }
     }
 
-    /// The `parse_address_number_suffix` function peeks at the next value in the input, checking if
-    /// the second character in the string is non-alphanumeric.  Since address number suffixes in Grants Pass
-    /// take values of either `1/2` or `3/4`, the second character will be `/`, which is not a valid
-    /// character for any components of the street name.  So if a `/` is present, it must be an address
-    /// number suffix, and this function will parse and return it.  If no address number suffix is
-    /// present, the function returns `None`.
+    /// The `address_number_range` function reads the upper bound of an address range, such as the
+    /// "-2503" in "2501-2503", returned as an i64 value.  A hyphen followed by a fraction, as in
+    /// "12-1/2", is an address number suffix rather than a range, and the function returns `None`
+    /// with the input unchanged.  An upper bound less than the address number `from`, as in
+    /// "1205-3", is not a range either, and the input is left for the subaddress parser.
+    #[tracing::instrument(skip_all)]
+    pub fn address_number_range(input: &str, from: i64) -> IResult<&str, Option<i64>> {
+        let mut range = (
+            complete::space0::<&str, nom::error::Error<_>>,
+            tag("-"),
+            complete::space0,
+            combinator::map_res(complete::digit1, str::parse::<i64>),
+            combinator::not(tag("/")),
+        );
+        match range.parse(input) {
+            Ok((rem, (_, _, _, to, _))) if to >= from => Ok((rem, Some(to))),
+            _ => Ok((input, None)),
+        }
+    }
+
+    /// The `address_number_fraction` function reads a fractional address number suffix such as
+    /// "1/2", optionally joined to the address number by a hyphen, as in "12-1/2".  The unicode
+    /// vulgar fractions "½", "¼" and "¾" read as "1/2", "1/4" and "3/4".
+    #[tracing::instrument(skip_all)]
+    pub fn address_number_fraction(input: &str) -> IResult<&str, Option<&str>> {
+        let (remaining, _) = complete::space0(input)?;
+        let (rest, _) = combinator::opt(tag("-")).parse(remaining)?;
+        let (rest, _) = complete::space0(rest)?;
+        let vulgar = branch::alt((
+            combinator::value("1/2", tag::<&str, &str, nom::error::Error<_>>("½")),
+            combinator::value("1/4", tag("¼")),
+            combinator::value("3/4", tag("¾")),
+        ))
+        .parse(rest);
+        if let Ok((rem, fraction)) = vulgar {
+            return Ok((rem, Some(fraction)));
+        }
+        let fraction = combinator::recognize((
+            complete::digit1::<&str, nom::error::Error<_>>,
+            tag("/"),
+            complete::digit1,
+        ))
+        .parse(rest);
+        match fraction {
+            Ok((rem, fraction)) if rem.is_empty() || rem.starts_with([' ', ',']) => {
+                Ok((rem, Some(fraction)))
+            }
+            _ => Ok((remaining, None)),
+        }
+    }
+
+    /// The `parse_address_number_suffix` function reads the address number suffix following the
+    /// address number.  Fractional suffixes such as `1/2` or `3/4`, the most common form, read
+    /// using [`Parse::address_number_fraction`].  Otherwise, the function peeks at the next value
+    /// in the input, checking if the second character in the string is non-alphanumeric.  Since
+    /// non-alphanumeric characters are not valid for any components of the street name, if present
+    /// the word must be an address number suffix, and this function will parse and return it.  If no
+    /// address number suffix is present, the function returns `None`.
     ///
     /// Note this approach is not valid for address number suffixes that do not conform to the
     /// indicated pattern.
     #[tracing::instrument(skip_all)]
     pub fn address_number_suffix(input: &str) -> IResult<&str, Option<&str>> {
+        if let (rem, Some(fraction)) = Self::address_number_fraction(input)? {
+            return Ok((rem, Some(fraction)));
+        }
         // Strip preceding whitespace
         let (remaining, _) = complete::space0(input)?;
         // Assumes no commas or dashes, space delimited from the street name
@@ -48,9 +106,9 @@ impl Parse {
             if suffix.len() > 1 {
                 // The second character in the sequence is not alaphanumeric if it is a "/"
                 let test = suffix.as_bytes()[1];
-                // Screen out periods so it does not confuse suffixes with N.W. or S.E. patterns
-                // match !is_alphanumeric(test) && test != b'.' {
-                match !test.is_alphanum() && test != b'.' {
+                // Screen out periods so it does not confuse suffixes with N.W. or S.E. patterns,
+                // and apostrophes so it does not confuse suffixes with names like O'BRIEN.
+                match !test.is_alphanum() && test != b'.' && test != b'\'' {
                     true => Ok((rem, Some(suffix))),
                     // If not a suffix, return the remainder before trying to parse the suffix
                     false => Ok((remaining, None)),
@@ -194,6 +252,29 @@ impl Parse {
         }
     }
 
+    /// The `post_directional` method attempts to parse the next word in the input as a street name
+    /// post directional, such as the "NW" in "100 MAIN ST NW".  Post directionals take the same
+    /// values as predirectionals, so the method returns a [`StreetNamePreDirectional`].  Returns
+    /// the full input if no post directional is present, or if the next word begins a postal
+    /// community.
+    #[tracing::instrument(skip_all)]
+    pub fn post_directional(input: &str) -> IResult<&str, Option<StreetNamePreDirectional>> {
+        // Strip preceding comma.
+        if let Ok((_, Some(_))) =
+            combinator::opt(tag::<&str, &str, nom::error::Error<_>>(",")).parse(input.trim_start())
+        {
+            // A comma ends the complete street name.
+            return Ok((input, None));
+        }
+        // Postal communities can start with a directional, e.g. "North Bend".
+        let (_, community) = Self::is_postal_community(input)?;
+        if community {
+            tracing::trace!("Postal community follows the street name.");
+            return Ok((input, None));
+        }
+        Self::pre_directional(input)
+    }
+
     /// The `pre_modifier` method attempts to parse the next word in the input as a
     /// [`StreetNamePreModifier`] variant.  Returns the full input in no pre-modifier is present.
     #[tracing::instrument(skip_all)]
@@ -217,25 +298,65 @@ impl Parse {
     }
 
     /// The `pre_type` method attempts to parse the next word in the input as a
-    /// [`StreetNamePreType`] variant.  Returns the full input if no pre-type is present.
+    /// [`StreetNamePreType`] variant.  Returns the full input if no pre-type is present.  Highway
+    /// designators canonicalize to the FGDC pre type, with the route number left as the street
+    /// name, so "HWY 199", "US HIGHWAY 199" and "US-199" read as a highway pre type and street
+    /// name "199".
     #[tracing::instrument(skip_all)]
     pub fn pre_type(input: &str) -> IResult<&str, Option<StreetNamePreType>> {
         // Strip preceding whitespace.
         let (rem, _) = complete::space0(input)?;
-        // Take one or more alphabetic character.
-        if let Ok((rem, result)) = complete::alpha1::<&str, nom::error::Error<_>>(rem) {
-            // Attempt to read as pre-type.
-            let check = StreetNamePreType::match_mixed(result);
-            match check {
-                // If some, return the remainder and the value.
-                Some(value) => Ok((rem, Some(value))),
-                // If none, return the original input.
-                None => Ok((input, None)),
+        // Highway designators span up to three words, separated by spaces or periods, as in
+        // "U.S. HWY" or "STATE ROUTE".  Hold each word with the input remaining after it.
+        let mut words = Vec::new();
+        let mut rest = rem;
+        while words.len() < 3 {
+            let Ok((after, word)) = complete::alpha1::<&str, nom::error::Error<_>>(rest) else {
+                break;
+            };
+            words.push((word, after));
+            let (after, _) = combinator::opt(tag(".")).parse(after)?;
+            let (after, _) = complete::space0(after)?;
+            rest = after;
+        }
+        // Try the longest designator first.
+        for k in (1..=words.len()).rev() {
+            let phrase = words[..k]
+                .iter()
+                .map(|(word, _)| *word)
+                .collect::<Vec<&str>>()
+                .join(" ");
+            let Some(value) = StreetNamePreType::match_mixed(&phrase) else {
+                continue;
+            };
+            let after = words[k - 1].1;
+            // A route number may follow the designator after a period or hyphen, as in "OR-238".
+            let (numbered, _) = combinator::opt(tag(".")).parse(after)?;
+            let (numbered, _) = complete::space0(numbered)?;
+            let (numbered, _) = combinator::opt(tag("-")).parse(numbered)?;
+            let (numbered, _) = complete::space0(numbered)?;
+            let has_number = numbered.starts_with(|c: char| c.is_ascii_digit());
+            // Short designators like "US" or "OR" are only highways when a route number follows.
+            if Self::route_abbreviation(&phrase) && !has_number {
+                continue;
             }
-        } else {
-            // If parsing input fails, return the original input.
-            Ok((input, None))
+            tracing::trace!("Pre type read: {phrase}");
+            return match has_number && value.is_route() {
+                true => Ok((numbered, Some(value))),
+                false => Ok((after, Some(value))),
+            };
         }
+        // If none, return the original input.
+        Ok((input, None))
+    }
+
+    /// The `route_abbreviation` function returns true if `phrase` is a short highway designator
+    /// such as "US", "OR" or "I", which also read as words or initials in street names.
+    fn route_abbreviation(phrase: &str) -> bool {
+        matches!(
+            phrase.to_uppercase().as_str(),
+            "US" | "U S" | "OR" | "ORE" | "OREGON" | "I" | "IH" | "SR" | "HY"
+        )
     }
 
     /// The `separator` method attempts to parse the next word in the input as a
@@ -269,155 +390,112 @@ impl Parse {
     }
 
     /// The `street_name` method attempts to parse the next sequence of words in the input as a
-    /// street name.  After finding at least one word, will return if the next word in `input` is a
-    /// street name post type.
-    /// Screen for PO Boxes?
-    /// TODO: If no street name is present, but street type is present, this will categorize the
-    /// street type as a street name, because we do not check for post-type on the first pass.
-    /// Eg. West Street parses as directional: West, street name: Street.  Should parse as
-    /// directional: None, street name: West, street type: Street.
-    /// TODO: Only checks for two post types in succession, but streets like Park Plaza Drive have
-    /// three.
+    /// street name.  The method reads the words of the street segment, stopping at punctuation, a
+    /// postal community, or a subaddress type following a post type.  Since words like "PARK" and
+    /// "PLAZA" can be either part of the street name or a post type, the method then considers
+    /// each way to split the segment, and keeps the split before the last post type that leaves a
+    /// non-empty street name (e.g. "PARK PLAZA" and "DRIVE" from "PARK PLAZA DRIVE").  If no such
+    /// split exists, the full segment is the street name.  Ordinals in the street name convert to
+    /// the canonical form using [`OrdinalStyle`], so "SIXTH ST" and "6 ST" read as "6TH".
+    /// Mailing addresses such as PO boxes read using [`Parse::postal_delivery`] instead.
     #[tracing::instrument(skip_all)]
     pub fn street_name(input: &str) -> IResult<&str, Option<String>> {
-        // On the initial pass, we read the first word of the street name.
-        let mut name = String::new();
-        // Strip preceding whitespace.
-        let (rem, _) = complete::space0(input)?;
-        let mut remaining = rem;
-        // The next word of remaining may be part of the street name.
-        // It could also be a post type, subaddress, city, state or zip.
-        // Check if the next word parses as a post type.
-        let (_, mut cond) = Self::is_post_type(rem)?;
-        // If a post type is found, check to see if it is followed by a post type.
-        if cond {
-            let (first, _) = Self::post_type(rem)?;
-            let (_, next) = Self::is_post_type(first)?;
-            if next {
-                // If so, only the last type is the post type.
-                cond = false;
-            } else {
-                // Example: GARDEN VALLEY WAY
-                // First word is a post-type, second is not, third is.
-                // If the second word is not a post-type, check that the third is not as well.
-                tracing::trace!("First is {first}");
-                let (second, _) = complete::space0(first)?;
-                // if !second.is_empty() {
-                //     tracing::trace!("Second is {second}");
-                //     let (second, _) = space0(second)?;
-                //     let (_, second) = alphanumeric1(second)?;
-                //     let (_, next) = Self::is_post_type(second)?;
-                //     if next {
-                //         tracing::trace!("Post type confirmed.");
-                //         cond = false;
-                //     }
-                // }
-                match complete::alphanumeric1::<&str, nom::error::Error<_>>(second) {
-                    Ok((second, _)) => {
-                        tracing::trace!("Second is {second}");
-                        let (second, _) = complete::space0(second)?;
-                        let (_, second) = complete::alphanumeric1(second)?;
-                        let (_, next) = Self::is_post_type(second)?;
-                        if next {
-                            tracing::trace!("Post type confirmed.");
-                            cond = false;
-                        }
-                    }
-                    Err(e) => tracing::trace!("{}", e.to_string()),
-                }
-            }
-
-            // If the post type could also be a subaddress, parse as post type and not part of
-            // the street name.
-            if let Ok((_, Some(_))) = Self::subaddress_type(first) {
-                cond = true;
-            }
-        }
-        // Check if the next word parses as a postal community.
-        // let (_, check) = Self::is_postal_community(rem)?;
-        // if check {
-        //     // Cond is the variable that will control the while loop
-        //     cond = true;
-        // }
-        // If cond is false because input is empty, set to true
-        if combinator::eof::<&str, nom::error::Error<_>>(rem).is_ok() {
-            tracing::trace!("Eof detected.");
-            cond = true;
-        // Break if input is not alphanumeric.
-        } else if complete::alphanumeric1::<&str, nom::error::Error<_>>(rem).is_err() {
-            tracing::trace!("Nonalphanumeric characters detected: {}", rem);
-            cond = true;
-        }
-        tracing::trace!("Initial condition is {}", cond);
-        while !cond {
+        // Each word of the segment, with the input at the start and after the end of the word.
+        let mut words: Vec<(&str, &str, &str)> = Vec::new();
+        let mut remaining = input;
+        let mut post_type_seen = false;
+        loop {
             // Strip preceding whitespace.
             let (rem, _) = complete::space0(remaining)?;
-            // Take one or more alphabetic character.
-            if let Ok((rem, result)) = complete::alphanumeric1::<&str, nom::error::Error<_>>(rem) {
-                // Strip preceding whitespace.
-                let (rem, _) = complete::space0(rem)?;
-                // Read has succeeded, reset remainder.
-                remaining = rem;
-                if !name.is_empty() {
-                    // Push parsed word to street name.
-                    name.push(' ');
-                }
-                name.push_str(result);
-                tracing::trace!("Working name: {}", name);
-                // Capture apostrophes in street names.
-                tracing::trace!("Apostrophe check on {}", remaining);
-                let (rem, apostrophe) = combinator::opt(tag("'")).parse(remaining)?;
-                if let Some(value) = apostrophe {
-                    tracing::trace!("Apostrophe found, rem: {}", rem);
-                    name.push_str(value);
-                    // Could probably just tack on an 'S' here.
-                    // Skipping check for other types because it follows an apostrophe.
-                    if let Ok((rem, result)) = complete::alpha1::<&str, nom::error::Error<_>>(rem) {
-                        name.push_str(result);
-                        remaining = rem;
-                    }
+            if !words.is_empty() {
+                // If next word is a postal community, end the segment.
+                let (_, check) = Self::is_postal_community(rem)?;
+                if check {
+                    tracing::trace!("Postal community detected.");
+                    break;
                 }
-                // If next word is a post type, end loop.
-                (_, cond) = Self::is_post_type(rem)?;
-                // If a post type is found, check to see if it is followed by a post type.
-                if cond {
-                    let (first, _) = Self::post_type(rem)?;
-                    let (_, next) = Self::is_post_type(first)?;
-                    if next {
-                        tracing::trace!("Followed by post type.");
-                        // If so, only the last type is the post type.
-                        cond = false;
-                    }
-                    // If the post type could also be a subaddress, parse as post type and not part of
-                    // the street name.
-                    if let Ok((_, Some(_))) = Self::subaddress_type(first) {
+                // If a subaddress type follows a post type, the post type ends the street name.
+                if post_type_seen {
+                    if let Ok((_, Some(_))) = Self::subaddress_type(rem) {
                         tracing::trace!("Potential subaddress type found.");
-                        cond = true;
+                        break;
                     }
                 }
-                // If next word is a postal community, end loop.
-                let (_, check) = Self::is_postal_community(rem)?;
-                tracing::trace!("Postal community detected.");
-                if check {
-                    cond = true;
-                }
-                // End loop if at end of input.
-                if combinator::eof::<&str, nom::error::Error<_>>(rem).is_ok() {
-                    tracing::trace!("Eof detected.");
-                    cond = true;
-                // Break if input is not alphanumeric.
-                } else if complete::alphanumeric1::<&str, nom::error::Error<_>>(rem).is_err() {
-                    tracing::trace!("Nonalphanumeric characters detected.");
-                    cond = true;
+            }
+            // Take one or more alphanumeric characters, ending at punctuation or end of input.
+            let Ok((mut after, _)) = complete::alphanumeric1::<&str, nom::error::Error<_>>(rem)
+            else {
+                tracing::trace!("End of street segment: {}", rem);
+                break;
+            };
+            // Capture apostrophes in street names.
+            if let Ok((rest, _)) = (
+                tag::<&str, &str, nom::error::Error<_>>("'"),
+                complete::alpha1,
+            )
+                .parse(after)
+            {
+                tracing::trace!("Apostrophe found, rem: {}", rest);
+                after = rest;
+            }
+            // Capture hyphenated ordinals like "TWENTY-FIRST".
+            if let Ok((rest, _)) = (
+                tag::<&str, &str, nom::error::Error<_>>("-"),
+                complete::alpha1,
+            )
+                .parse(after)
+            {
+                if OrdinalStyle::is_ordinal(&rem[..rem.len() - rest.len()]) {
+                    after = rest;
                 }
             }
+            let word = &rem[..rem.len() - after.len()];
+            tracing::trace!("Segment word: {}", word);
+            if StreetNamePostType::match_mixed(word).is_some() {
+                post_type_seen = true;
+            }
+            words.push((word, rem, after));
+            remaining = after;
         }
-        tracing::trace!("Rem: {}", remaining);
-        if name.is_empty() {
-            Ok((input, None))
-        } else {
-            Ok((remaining, Some(name.to_uppercase())))
+        if words.is_empty() {
+            return Ok((input, None));
         }
+        // Split before the last post type, so that the street name is not empty.
+        let split = (1..words.len())
+            .rev()
+            .find(|i| StreetNamePostType::match_mixed(words[*i].0).is_some())
+            .unwrap_or(words.len());
+        let name = words[..split]
+            .iter()
+            .map(|(word, _, _)| *word)
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_uppercase();
+        // Convert ordinals to the canonical form.  A bare number is only an ordinal when a post
+        // type follows, as in "6 ST", since highway routes like "HWY 199" also end in numbers.
+        let name = match split < words.len() {
+            true => OrdinalStyle::canonical_numbered(&name),
+            false => OrdinalStyle::canonical(&name),
+        };
+        let remaining = match words.get(split) {
+            Some((_, start, _)) => start,
+            None => complete::space0(words[split - 1].2)?.0,
+        };
+        tracing::trace!("Rem: {}", remaining);
+        Ok((remaining, Some(name)))
+    }
+
+    /// The `is_complete_street` function returns true if the input parses to a complete street
+    /// name with a non-empty street name and a valid post type, ignoring any predirectional.
+    /// Peeks at the data without consuming it.
+    #[tracing::instrument(skip_all)]
+    pub fn is_complete_street(input: &str) -> IResult<&str, bool> {
+        let (rem, _) = Self::pre_modifier(input)?;
+        let (rem, _) = Self::pre_type(rem)?;
+        let (rem, _) = Self::separator(rem)?;
+        let (rem, name) = Self::street_name(rem)?;
+        let (_, post_type) = Self::post_type(rem)?;
+        Ok((input, name.is_some() && post_type.is_some()))
     }
 
     /// The `post_type` function attempts to parse the next word in the input as a
@@ -457,6 +535,109 @@ impl Parse {
         }
     }
 
+    /// The `postal_delivery` function reads a non-physical mailing address at the start of the
+    /// input, such as "PO BOX 123", "RR 2 BOX 152", "HC 68 BOX 23A" or "GENERAL DELIVERY".  The
+    /// designator may be abbreviated with or without periods ("P.O. BOX", "POB", "HCR").  A bare
+    /// "PO" or "BOX" designator reads only when followed by a box number, so that street names like
+    /// "BOX CANYON RD" still read as streets.  If no mailing address is present, the function
+    /// returns `None` and the full input.
+    #[tracing::instrument(skip_all)]
+    pub fn postal_delivery(input: &str) -> IResult<&str, Option<PostalDelivery>> {
+        let Some((rem, delivery_type)) = Self::postal_delivery_designator(input) else {
+            return Ok((input, None));
+        };
+        tracing::trace!("Postal delivery type: {delivery_type}");
+        let mut delivery = PostalDelivery {
+            delivery_type,
+            route: None,
+            box_id: None,
+        };
+        let mut rem = rem;
+        match delivery_type {
+            PostalDeliveryType::GeneralDelivery => {}
+            PostalDeliveryType::PoBox => {
+                let (next, box_id) = Self::postal_delivery_id(rem)?;
+                delivery.box_id = box_id;
+                rem = next;
+            }
+            PostalDeliveryType::RuralRoute | PostalDeliveryType::HighwayContract => {
+                let (next, route) = Self::postal_delivery_id(rem)?;
+                delivery.route = route;
+                rem = next;
+                let (next, _) = complete::space0(rem)?;
+                if let Ok((next, _)) = branch::alt((
+                    tag_no_case::<&str, &str, nom::error::Error<_>>("BOX"),
+                    tag("#"),
+                ))
+                .parse(next)
+                {
+                    let (next, box_id) = Self::postal_delivery_id(next)?;
+                    if box_id.is_some() {
+                        delivery.box_id = box_id;
+                        rem = next;
+                    }
+                }
+            }
+        }
+        Ok((rem, Some(delivery)))
+    }
+
+    /// The `postal_delivery_designator` function reads the designator of a mailing address, trying
+    /// the longest run of words first, and returns the remainder and the delivery type.
+    fn postal_delivery_designator(input: &str) -> Option<(&str, PostalDeliveryType)> {
+        let start = input.trim_start();
+        // Byte offsets of the end of each of the first four words.
+        let mut ends = Vec::new();
+        let mut rest = start;
+        while ends.len() < 4 {
+            let word = rest
+                .find(|c: char| !(c.is_ascii_alphabetic() || c == '.'))
+                .unwrap_or(rest.len());
+            if word == 0 {
+                break;
+            }
+            let end = start.len() - rest.len() + word;
+            ends.push(end);
+            rest = start[end..].trim_start_matches(' ');
+        }
+        for end in ends.into_iter().rev() {
+            let phrase = &start[..end];
+            let rem = &start[end..];
+            if let Some(delivery_type) = PostalDeliveryType::match_mixed(phrase) {
+                let bare = matches!(
+                    phrase.to_uppercase().replace('.', "").as_str(),
+                    "PO" | "BOX"
+                );
+                let numbered = rem
+                    .trim_start_matches([' ', '#'])
+                    .starts_with(|c: char| c.is_ascii_digit());
+                if !bare || numbered {
+                    return Some((rem, delivery_type));
+                }
+            }
+        }
+        None
+    }
+
+    /// The `postal_delivery_id` function reads a route number or box identifier, stripping a
+    /// preceding number sign.
+    fn postal_delivery_id(input: &str) -> IResult<&str, Option<String>> {
+        let (rem, _) = complete::space0(input)?;
+        let (rem, _) = combinator::opt(tag("#")).parse(rem)?;
+        let (rem, _) = complete::space0(rem)?;
+        match combinator::recognize((
+            complete::alphanumeric1::<&str, nom::error::Error<_>>,
+            combinator::opt((tag("-"), complete::alphanumeric1)),
+        ))
+        .parse(rem)
+        {
+            Ok((rem, id)) if id.chars().any(|c| c.is_ascii_digit()) => {
+                Ok((rem, Some(id.to_uppercase())))
+            }
+            _ => Ok((input, None)),
+        }
+    }
+
     /// The `subaddress_type` function attempts to find a word following the street name post
     /// type and preceding the postal community.  If a word is present, and parses to a subaddress
     /// type, the function will return the type and the remainder.  If no subaddress type is present,
@@ -486,6 +667,81 @@ impl Parse {
         }
     }
 
+    /// The `subaddress_token` function reads a single subaddress identifier word, such as the "3"
+    /// in "BLDG 3", stripping a preceding number sign.  The word must end the subaddress, being
+    /// followed by the end of input, a comma, a postal community, a zip code or another subaddress
+    /// element, otherwise the function returns `None` and the full input.
+    fn subaddress_token(input: &str) -> IResult<&str, Option<&str>> {
+        let (rem, _) = complete::space0(input)?;
+        let (rem, _) = combinator::opt(tag("#")).parse(rem)?;
+        let (rem, _) = complete::space0(rem)?;
+        if let Ok((rem, token)) = complete::alphanumeric1::<&str, nom::error::Error<_>>(rem) {
+            let next = rem.trim_start();
+            let bounded = next.is_empty()
+                || next.starts_with(',')
+                || Self::is_postal_community(next)?.1
+                || Self::is_zip(next)?.1
+                || Self::subaddress_type(next)?.1.is_some();
+            if bounded {
+                return Ok((rem, Some(token)));
+            }
+        }
+        Ok((input, None))
+    }
+
+    /// The `building` function reads a building subaddress element, such as "BLDG 3", returning
+    /// the building identifier.  Reading the building separately from the unit allows chains of
+    /// subaddress elements like "BLDG 3 APT 12".  If no building is present, or the identifier
+    /// continues past one word, the function returns `None` and the full input.
+    #[tracing::instrument(skip_all)]
+    pub fn building(input: &str) -> IResult<&str, Option<String>> {
+        if let (rem, Some(SubaddressType::Building)) = Self::subaddress_type(input)? {
+            if let (rem, Some(token)) = Self::subaddress_token(rem)? {
+                return Ok((rem, Some(token.to_string())));
+            }
+        }
+        Ok((input, None))
+    }
+
+    /// The `floor` function reads a floor subaddress element with a numeric identifier, such as
+    /// "FL 2", returning the floor number.  If no floor is present, the function returns `None` and
+    /// the full input.
+    #[tracing::instrument(skip_all)]
+    pub fn floor(input: &str) -> IResult<&str, Option<i64>> {
+        if let (rem, Some(SubaddressType::Floor)) = Self::subaddress_type(input)? {
+            if let (rem, Some(token)) = Self::subaddress_token(rem)? {
+                if let Ok(floor) = token.parse::<i64>() {
+                    return Ok((rem, Some(floor)));
+                }
+            }
+        }
+        Ok((input, None))
+    }
+
+    /// The `subaddress_range` function reads a range of unit identifiers, such as "100-104" or
+    /// "A-C", returned with the whitespace around the hyphen removed.  Both ends of the range must
+    /// be numbers, or both single letters.  See [`crate::PartialAddress::subaddress_identifiers`]
+    /// for the expansion of the range into units.
+    #[tracing::instrument(skip_all)]
+    pub fn subaddress_range(input: &str) -> IResult<&str, Option<String>> {
+        let mut range = (
+            complete::alphanumeric1::<&str, nom::error::Error<_>>,
+            complete::space0,
+            tag("-"),
+            complete::space0,
+            complete::alphanumeric1,
+        );
+        if let Ok((rem, (from, _, _, _, to))) = range.parse(input) {
+            let numeric = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+            let letter = |s: &str| s.len() == 1 && s.chars().all(|c| c.is_ascii_alphabetic());
+            let ends = rem.is_empty() || rem.starts_with([' ', ',']);
+            if ends && ((numeric(from) && numeric(to)) || (letter(from) && letter(to))) {
+                return Ok((rem, Some(format!("{from}-{to}"))));
+            }
+        }
+        Ok((input, None))
+    }
+
     /// The `subaddress_id` function attempts to find a word following the street name post
     /// type and preceding the postal community.  If a word is present, and parses to a subaddress
     /// type, the function will return the type and the remainder.  If no subaddress type is present,
@@ -503,6 +759,10 @@ impl Parse {
         let (rem, _) = combinator::opt(tag("-")).parse(rem)?;
         // Strip whitespace between symbol and id.
         let (rem, _) = complete::space0(rem)?;
+        // A range of units reads as a single identifier.
+        if let (rem, Some(range)) = Self::subaddress_range(rem)? {
+            return Ok((rem, Some(range)));
+        }
         // If there is no subaddress, we expect the city name next.
         let (_, mut cond) = Self::is_postal_community(rem)?;
         // Could be a state name instead of a subaddress.
@@ -552,7 +812,9 @@ impl Parse {
                 // let (_, state) = Self::is_state(rem)?;
                 // Could be a zip code.
                 let (_, zip) = Self::is_zip(rem)?;
-                cond |= zip | comm;
+                // Could be a building or floor following the unit, as in "APT 12 BLDG 3".
+                let level = Self::building(rem)?.1.is_some() || Self::floor(rem)?.1.is_some();
+                cond |= zip | comm | level;
                 // cond = comm | state | zip;
                 // End loop if at end of input.
                 if combinator::eof::<&str, nom::error::Error<_>>(rem).is_ok() {
@@ -712,69 +974,291 @@ impl Parse {
         }
     }
 
+    /// The `complete_street_name` function reads a complete street name without an address number,
+    /// such as "NE 7TH ST" or "HWY 199", from the start of the input.  If no street name is
+    /// present, the function returns `None` and the full input.
+    #[tracing::instrument(skip_all)]
+    pub fn complete_street_name(input: &str) -> IResult<&str, Option<CompleteStreetName>> {
+        let (rem, pre_directional) = Self::pre_directional(input)?;
+        let (rem, pre_modifier) = Self::pre_modifier(rem)?;
+        let (rem, pre_type) = Self::pre_type(rem)?;
+        let (rem, separator) = Self::separator(rem)?;
+        let (rem, street_name) = Self::street_name(rem)?;
+        let Some(street_name) = street_name else {
+            tracing::trace!("No street name read.");
+            return Ok((input, None));
+        };
+        let (rem, post_type) = Self::post_type(rem)?;
+        let (rem, post_directional) = Self::post_directional(rem)?;
+        let street = CompleteStreetName {
+            pre_directional,
+            pre_modifier,
+            pre_type,
+            separator,
+            street_name,
+            post_type,
+            post_directional,
+        };
+        Ok((rem, Some(street)))
+    }
+
+    /// The `intersection` function reads a location given by two cross streets, such as
+    /// "6TH ST & G ST" or "NE 7TH / F ST", optionally followed by a postal community.  The cross
+    /// streets may be joined by "&", "/", "@", "AND" or "AT".  Use [`Intersection::resolve`] to
+    /// estimate the location of the intersection.  If the input does not read as two cross
+    /// streets, the function returns `None` and the full input.
+    #[tracing::instrument(skip_all)]
+    pub fn intersection(input: &str) -> IResult<&str, Option<Intersection>> {
+        let Some((first, second)) = Self::intersection_split(input) else {
+            tracing::trace!("No intersection connector found.");
+            return Ok((input, None));
+        };
+        let (first_rem, first) = Self::complete_street_name(first)?;
+        let (rem, second) = Self::complete_street_name(second)?;
+        let (Some(first), Some(second)) = (first, second) else {
+            return Ok((input, None));
+        };
+        // The first street must end at the connector.
+        if !first_rem.trim().is_empty() {
+            tracing::trace!("Unread input before connector: {first_rem}");
+            return Ok((input, None));
+        }
+        let (_, community) = Self::is_postal_community(rem)?;
+        let (rem, postal_community) = match community {
+            true => Self::postal_community(rem)?,
+            false => (rem, None),
+        };
+        let intersection = Intersection {
+            first,
+            second,
+            postal_community,
+        };
+        tracing::trace!("Intersection read: {intersection}");
+        Ok((rem, Some(intersection)))
+    }
+
+    /// The `intersection_split` function splits the input at the first connector between cross
+    /// streets, returning the input before the connector and the input after it.  The word
+    /// connectors "AND" and "AT" must stand alone, so that street names like "SANDY" and "ATLAS"
+    /// do not split.
+    fn intersection_split(input: &str) -> Option<(&str, &str)> {
+        for (i, _) in input.char_indices().skip(1) {
+            let rest = &input[i..];
+            let word_start = input[..i].ends_with(char::is_whitespace);
+            let connector = match word_start {
+                true => branch::alt((
+                    tag::<&str, &str, nom::error::Error<_>>("&"),
+                    tag("/"),
+                    tag("@"),
+                    nom::sequence::terminated(
+                        branch::alt((tag_no_case("AND"), tag_no_case("AT"))),
+                        complete::space1,
+                    ),
+                ))
+                .parse(rest),
+                false => branch::alt((
+                    tag::<&str, &str, nom::error::Error<_>>("&"),
+                    tag("/"),
+                    tag("@"),
+                ))
+                .parse(rest),
+            };
+            if let Ok((after, _)) = connector {
+                return Some((&input[..i], after));
+            }
+        }
+        None
+    }
+
     /// The `address` function attempts to read the complete address and parse it into its
-    /// constituent components.
+    /// constituent components.  To see which parts of the input were used for each component,
+    /// see [`Parse::address_diagnostics`].
     #[tracing::instrument(skip_all)]
     pub fn address(input: &str) -> IResult<&str, PartialAddress> {
+        Self::address_traced(input, &mut |_, _, _, _| {})
+    }
+
+    /// The `address_traced` function parses the complete address in `input` like
+    /// [`Parse::address`], calling `trace` after each component parser with the component, the
+    /// input before and after the parser, and whether the parser produced a value.
+    pub(crate) fn address_traced<'a, F>(
+        input: &'a str,
+        trace: &mut F,
+    ) -> IResult<&'a str, PartialAddress>
+    where
+        F: FnMut(AddressComponent, &'a str, &'a str, bool),
+    {
+        use AddressComponent as C;
         // When reading a partial address, any field can fail, so we cannot use the question mark
         // operator or it will short circuit cases where we correctly infer None when given an
         // invalid string.
         // this struct will hold the values of the parsed address components
         let mut address = PartialAddress::default();
+        // mailing addresses like "PO BOX 123" have no street, so read the postal community next
+        let (rem, delivery) = Self::postal_delivery(input)?;
+        if let Some(delivery) = delivery {
+            tracing::trace!("Postal delivery: {}", delivery.label());
+            trace(C::PostalDelivery, input, rem, true);
+            address.set_postal_delivery(Some(delivery));
+            return Self::address_locality(rem, address, trace);
+        }
         // attempt to read the complete address number
         let (rem, address_number) = Self::address_number(input)?;
         if let Some(num) = &address_number {
             tracing::trace!("Address number: {num}");
         }
+        // the upper bound of an address range belongs to the address number span
+        let (rem, address_number_to) = match address_number {
+            Some(from) => Self::address_number_range(rem, from)?,
+            None => (rem, None),
+        };
+        if let Some(to) = &address_number_to {
+            tracing::trace!("Address number range ends at {to}");
+        }
+        trace(C::AddressNumber, input, rem, address_number.is_some());
         // we avoid an if let clause because address_number is none if not present.
         address.address_number = address_number;
-        let (rem, suffix) = Self::address_number_suffix(rem)?;
+        address.address_number_to = address_number_to;
+        let (next, suffix) = Self::address_number_suffix(rem)?;
         if let Some(s) = &suffix {
             tracing::trace!("Address number suffix: {s}");
         } else {
             tracing::trace!("No address number suffix detected.");
         }
+        trace(C::AddressNumberSuffix, rem, next, suffix.is_some());
         address.set_address_number_suffix(suffix);
+        let rem = next;
         tracing::trace!("Reading pre directional.");
-        let (rem, directional) = Self::pre_directional(rem)?;
+        let (mut next, mut directional) = Self::pre_directional(rem)?;
+        // If reading the directional leaves no street name or post type, but reading the
+        // directional as the street name does not (e.g. "NORTH ST"), backtrack.
+        if directional.is_some() && !Self::is_complete_street(next)?.1 {
+            let (_, complete) = Self::is_complete_street(rem)?;
+            if complete {
+                tracing::trace!("Reading directional as part of the street name.");
+                directional = None;
+                next = rem;
+            }
+        }
         tracing::trace!("Street name pre-directional: {:#?}", &directional);
+        trace(C::PreDirectional, rem, next, directional.is_some());
         address.street_name_pre_directional = directional;
+        let rem = next;
         tracing::trace!("Reading pre modifier.");
-        let (rem, premod) = Self::pre_modifier(rem)?;
+        let (next, premod) = Self::pre_modifier(rem)?;
         tracing::trace!("Street name pre-modifier: {:#?}", &premod);
+        trace(C::PreModifier, rem, next, premod.is_some());
         address.pre_modifier = premod;
+        let rem = next;
         tracing::trace!("Reading pre type.");
-        let (rem, pretype) = Self::pre_type(rem)?;
+        let (next, pretype) = Self::pre_type(rem)?;
         tracing::trace!("Street name pre-type: {:#?}", &pretype);
+        trace(C::PreType, rem, next, pretype.is_some());
         address.pre_type = pretype;
+        let rem = next;
         tracing::trace!("Reading pre type separator.");
-        let (rem, separator) = Self::separator(rem)?;
+        let (next, separator) = Self::separator(rem)?;
         tracing::trace!("Street name separator: {:#?}", &separator);
+        trace(C::Separator, rem, next, separator.is_some());
         address.separator = separator;
+        let rem = next;
         tracing::trace!("Reading street name.");
-        let (rem, name) = Self::street_name(rem)?;
+        let (next, name) = Self::street_name(rem)?;
         tracing::trace!("Street name element: {:#?}", &name);
+        trace(C::StreetName, rem, next, name.is_some());
         address.street_name = name;
+        let rem = next;
         tracing::trace!("Reading post type.");
-        let (rem, post_type) = Self::post_type(rem)?;
+        let (next, post_type) = Self::post_type(rem)?;
         tracing::trace!("Street name post-type: {:#?}", &post_type);
+        trace(C::PostType, rem, next, post_type.is_some());
         address.street_name_post_type = post_type;
-        let (rem, sub_type) = Self::subaddress_type(rem)?;
+        let rem = next;
+        tracing::trace!("Reading post directional.");
+        let (next, post_directional) = Self::post_directional(rem)?;
+        tracing::trace!("Street name post-directional: {:#?}", &post_directional);
+        trace(C::PostDirectional, rem, next, post_directional.is_some());
+        address.street_name_post_directional = post_directional;
+        let rem = Self::subaddress_levels(next, &mut address, trace)?;
+        let (next, sub_type) = Self::subaddress_type(rem)?;
         tracing::trace!("Subaddress type: {:#?}", &sub_type);
+        trace(C::SubaddressType, rem, next, sub_type.is_some());
         address.subaddress_type = sub_type;
-        let (rem, sub_id) = Self::subaddress_id(rem)?;
+        let rem = next;
+        let (next, sub_id) = Self::subaddress_id(rem)?;
         tracing::trace!("Subaddress id: {:#?}", &sub_id);
+        trace(C::SubaddressIdentifier, rem, next, sub_id.is_some());
         address.subaddress_identifier = sub_id;
-        let (rem, post_comm) = Self::postal_community(rem)?;
+        let rem = Self::subaddress_levels(next, &mut address, trace)?;
+        Self::address_locality(rem, address, trace)
+    }
+
+    /// The `address_locality` function reads the postal community, state and zip code from `rem`
+    /// into `address`, calling `trace` after each component parser.
+    fn address_locality<'a, F>(
+        rem: &'a str,
+        mut address: PartialAddress,
+        trace: &mut F,
+    ) -> IResult<&'a str, PartialAddress>
+    where
+        F: FnMut(AddressComponent, &'a str, &'a str, bool),
+    {
+        use AddressComponent as C;
+        let (next, post_comm) = Self::postal_community(rem)?;
         tracing::trace!("Postal community: {:#?}", &post_comm);
+        trace(C::PostalCommunity, rem, next, post_comm.is_some());
         address.postal_community = post_comm;
-        let (rem, state) = Self::state(rem)?;
-        tracing::trace!("Postal community: {:#?}", &state);
+        let rem = next;
+        let (next, state) = Self::state(rem)?;
+        tracing::trace!("State: {:#?}", &state);
+        trace(C::State, rem, next, state.is_some());
         address.state_name = state;
-        let (rem, zip) = Self::zip(rem)?;
+        let rem = next;
+        let (next, zip) = Self::zip(rem)?;
         tracing::trace!("Zip code: {:#?}", &zip);
+        trace(C::Zip, rem, next, zip.is_some());
         address.zip_code = zip;
-        Ok((rem, address))
+        Ok((next, address))
+    }
+
+    /// The `subaddress_levels` function reads any building and floor elements at the start of
+    /// `rem`, such as the "BLDG 3 FL 2" in "BLDG 3 FL 2 STE 200", assigning them to `address` and
+    /// calling `trace` for each element.  Returns the remainder after the elements.
+    fn subaddress_levels<'a, F>(
+        mut rem: &'a str,
+        address: &mut PartialAddress,
+        trace: &mut F,
+    ) -> Result<&'a str, nom::Err<nom::error::Error<&'a str>>>
+    where
+        F: FnMut(AddressComponent, &'a str, &'a str, bool),
+    {
+        loop {
+            if let (next, Some(building)) = Self::building(rem)? {
+                tracing::trace!("Building: {building}");
+                trace(AddressComponent::Building, rem, next, true);
+                address.building = Some(building);
+                rem = next;
+            } else if let (next, Some(floor)) = Self::floor(rem)? {
+                tracing::trace!("Floor: {floor}");
+                trace(AddressComponent::Floor, rem, next, true);
+                address.floor = Some(floor);
+                rem = next;
+            } else {
+                return Ok(rem);
+            }
+        }
+    }
+
+    /// The `spelled_directional` function returns true if the next word in the input is a
+    /// directional spelled out in full, such as "WEST", rather than a postal abbreviation.
+    pub(crate) fn spelled_directional(input: &str) -> bool {
+        match complete::alpha1::<&str, nom::error::Error<_>>(input.trim_start()) {
+            Ok((_, word)) => {
+                StreetNamePreDirectional::match_abbreviated(word).is_none()
+                    && StreetNamePreDirectional::match_mixed(word).is_some()
+            }
+            Err(_) => false,
+        }
     }
 }
 
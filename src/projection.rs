@@ -0,0 +1,255 @@
+//! The `projection` module converts coordinates between geographic and projected coordinate
+//! systems used by address sources.
+use crate::{Cartesian, Geographic};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+/// Semi-major axis of the WGS84 and GRS80 ellipsoids, in meters.
+const SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+/// Flattening of the GRS80 ellipsoid, used by NAD83.
+const GRS80_FLATTENING: f64 = 1.0 / 298.257_222_101;
+/// Length of the international foot, in meters.
+const FOOT: f64 = 0.3048;
+/// Latitude limit of the Web Mercator projection, in degrees.
+const WEB_MERCATOR_LIMIT: f64 = 85.051_128_779_806_59;
+
+/// The `Projection` enum designates the coordinate systems supported for conversion.  Datum
+/// shifts between WGS84 and NAD83 are not modeled, introducing an error of about a meter, which is
+/// below the accuracy of the address points.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    derive_more::Display,
+    derive_more::FromStr,
+)]
+pub enum Projection {
+    /// Geographic coordinates in decimal degrees (EPSG:4326).  The `x` value is the longitude and
+    /// the `y` value is the latitude.
+    Wgs84,
+    /// Spherical Web Mercator in meters (EPSG:3857), used by the city address points and business
+    /// licenses.
+    #[default]
+    WebMercator,
+    /// NAD83 Oregon State Plane South in international feet (EPSG:2270).
+    OregonSouth,
+}
+
+impl Projection {
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `Projection`, accepting the variant name in snake case or the EPSG code.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "wgs84" | "4326" | "epsg:4326" => Some(Self::Wgs84),
+            "web_mercator" | "3857" | "epsg:3857" => Some(Self::WebMercator),
+            "oregon_south" | "2270" | "epsg:2270" => Some(Self::OregonSouth),
+            _ => None,
+        }
+    }
+
+    /// The `to_geographic` method converts the coordinates `x` and `y` from `self` to geographic
+    /// coordinates, returned as a (longitude, latitude) tuple in decimal degrees.
+    pub fn to_geographic(&self, x: f64, y: f64) -> (f64, f64) {
+        match self {
+            Self::Wgs84 => (x, y),
+            Self::WebMercator => {
+                let longitude = (x / SEMI_MAJOR_AXIS).to_degrees();
+                let latitude = (2.0 * (y / SEMI_MAJOR_AXIS).exp().atan() - FRAC_PI_2).to_degrees();
+                (longitude, latitude)
+            }
+            Self::OregonSouth => LambertConic::oregon_south().inverse(x * FOOT, y * FOOT),
+        }
+    }
+
+    /// The `from_geographic` method converts geographic coordinates `longitude` and `latitude`, in
+    /// decimal degrees, to the coordinate system of `self`, returned as an (x, y) tuple.
+    pub fn from_geographic(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        match self {
+            Self::Wgs84 => (longitude, latitude),
+            Self::WebMercator => {
+                let latitude = latitude.clamp(-WEB_MERCATOR_LIMIT, WEB_MERCATOR_LIMIT);
+                let x = SEMI_MAJOR_AXIS * longitude.to_radians();
+                let y = SEMI_MAJOR_AXIS * (FRAC_PI_4 + latitude.to_radians() / 2.0).tan().ln();
+                (x, y)
+            }
+            Self::OregonSouth => {
+                let (x, y) = LambertConic::oregon_south().forward(longitude, latitude);
+                (x / FOOT, y / FOOT)
+            }
+        }
+    }
+
+    /// The `transform` method converts the coordinates `x` and `y` from `self` to the coordinate
+    /// system `to`, returned as an (x, y) tuple.
+    pub fn transform(&self, to: Projection, x: f64, y: f64) -> (f64, f64) {
+        if *self == to {
+            return (x, y);
+        }
+        let (longitude, latitude) = self.to_geographic(x, y);
+        to.from_geographic(longitude, latitude)
+    }
+}
+
+/// The `Point` struct holds a coordinate pair tagged with its coordinate system.  Converting the
+/// points from mixed sources to a common projection using [`Point::to`] lets
+/// [`Cartesian::distance`] compare them.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct Point {
+    /// The `x` field holds the X coordinate, or the longitude for geographic coordinates.
+    pub x: f64,
+    /// The `y` field holds the Y coordinate, or the latitude for geographic coordinates.
+    pub y: f64,
+    /// The `projection` field holds the coordinate system of `x` and `y`.
+    pub projection: Projection,
+}
+
+impl Point {
+    /// The `new` method creates a point at `x` and `y` in the coordinate system `projection`.
+    pub fn new(x: f64, y: f64, projection: Projection) -> Self {
+        Self { x, y, projection }
+    }
+
+    /// The `to` method returns the point converted to the coordinate system `projection`.
+    pub fn to(&self, projection: Projection) -> Self {
+        let (x, y) = self.projection.transform(projection, self.x, self.y);
+        Self::new(x, y, projection)
+    }
+}
+
+impl Cartesian for Point {
+    fn x(&self) -> f64 {
+        self.x
+    }
+
+    fn y(&self) -> f64 {
+        self.y
+    }
+}
+
+impl Geographic for Point {
+    fn latitude(&self) -> f64 {
+        self.projection.to_geographic(self.x, self.y).1
+    }
+
+    fn longitude(&self) -> f64 {
+        self.projection.to_geographic(self.x, self.y).0
+    }
+}
+
+/// The `LambertConic` struct holds the constants of a Lambert Conformal Conic projection with two
+/// standard parallels on the GRS80 ellipsoid, following Snyder, "Map Projections: A Working
+/// Manual" (1987), pp. 107-109.  Coordinates are in meters.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct LambertConic {
+    /// First eccentricity of the ellipsoid.
+    e: f64,
+    /// Cone constant.
+    n: f64,
+    /// Product of the semi-major axis and the mapping constant F.
+    af: f64,
+    /// Radius of the parallel of origin.
+    rho0: f64,
+    /// Central meridian, in radians.
+    lambda0: f64,
+    /// False easting, in meters.
+    false_easting: f64,
+    /// False northing, in meters.
+    false_northing: f64,
+}
+
+impl LambertConic {
+    /// The `new` method computes the projection constants from the standard parallels `phi1` and
+    /// `phi2`, the latitude of origin `phi0` and central meridian `lambda0`, in decimal degrees.
+    fn new(
+        phi1: f64,
+        phi2: f64,
+        phi0: f64,
+        lambda0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Self {
+        let e = (2.0 * GRS80_FLATTENING - GRS80_FLATTENING.powi(2)).sqrt();
+        let (phi1, phi2, phi0) = (phi1.to_radians(), phi2.to_radians(), phi0.to_radians());
+        let m1 = Self::m(e, phi1);
+        let m2 = Self::m(e, phi2);
+        let t1 = Self::t(e, phi1);
+        let t2 = Self::t(e, phi2);
+        let n = (m1.ln() - m2.ln()) / (t1.ln() - t2.ln());
+        let af = SEMI_MAJOR_AXIS * m1 / (n * t1.powf(n));
+        let rho0 = af * Self::t(e, phi0).powf(n);
+        Self {
+            e,
+            n,
+            af,
+            rho0,
+            lambda0: lambda0.to_radians(),
+            false_easting,
+            false_northing,
+        }
+    }
+
+    /// The `oregon_south` method returns the constants for NAD83 Oregon State Plane South
+    /// (EPSG:2270), with standard parallels at 44°00′ and 42°20′ and origin at 41°40′, −120°30′.
+    fn oregon_south() -> Self {
+        Self::new(
+            44.0,
+            42.0 + 20.0 / 60.0,
+            41.0 + 40.0 / 60.0,
+            -120.5,
+            1_500_000.0,
+            0.0,
+        )
+    }
+
+    /// Snyder equation 14-15.
+    fn m(e: f64, phi: f64) -> f64 {
+        phi.cos() / (1.0 - (e * phi.sin()).powi(2)).sqrt()
+    }
+
+    /// Snyder equation 15-9a.
+    fn t(e: f64, phi: f64) -> f64 {
+        let es = e * phi.sin();
+        (FRAC_PI_4 - phi / 2.0).tan() / ((1.0 - es) / (1.0 + es)).powf(e / 2.0)
+    }
+
+    /// The `forward` method projects `longitude` and `latitude` to (x, y) in meters.
+    fn forward(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        let rho = self.af * Self::t(self.e, latitude.to_radians()).powf(self.n);
+        let theta = self.n * (longitude.to_radians() - self.lambda0);
+        let x = self.false_easting + rho * theta.sin();
+        let y = self.false_northing + self.rho0 - rho * theta.cos();
+        (x, y)
+    }
+
+    /// The `inverse` method converts `x` and `y` in meters to (longitude, latitude).
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let dx = x - self.false_easting;
+        let dy = self.rho0 - (y - self.false_northing);
+        let rho = self.n.signum() * (dx.powi(2) + dy.powi(2)).sqrt();
+        let t = (rho / self.af).powf(1.0 / self.n);
+        let theta = (self.n.signum() * dx).atan2(self.n.signum() * dy);
+        let longitude = theta / self.n + self.lambda0;
+        // Snyder equation 7-9, solved by iteration.
+        let mut latitude = FRAC_PI_2 - 2.0 * t.atan();
+        for _ in 0..15 {
+            let es = self.e * latitude.sin();
+            let next = FRAC_PI_2 - 2.0 * (t * ((1.0 - es) / (1.0 + es)).powf(self.e / 2.0)).atan();
+            let converged = (next - latitude).abs() < 1e-12;
+            latitude = next;
+            if converged {
+                break;
+            }
+        }
+        (longitude.to_degrees(), latitude.to_degrees())
+    }
+}
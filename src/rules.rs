@@ -0,0 +1,365 @@
+//! The `rules` module loads rule sets for standardizing address components, converting the naming
+//! conventions of one addressing authority to those of another.
+use crate::{
+    from_csv, Address, AddressComponent, AddressErrorKind, Builder, Io, PartialAddress,
+    StreetNamePostType, StreetNamePreDirectional, SubaddressType,
+};
+use derive_more::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// The `NULL` value in a rule condition matches a missing component, and in a rewrite removes the
+/// component.
+const NULL: &str = "<Null>";
+
+/// The `DEFAULT_RULES` static holds the default rule set, parsed on first use.
+static DEFAULT_RULES: LazyLock<Rules> = LazyLock::new(|| {
+    Rules::from_toml_str(include_str!("../data/default_rules.toml"))
+        .expect("Default rules are valid.")
+});
+
+/// The `CITIFY_RULES` static holds the rule set used by `citify`, parsed on first use.
+static CITIFY_RULES: LazyLock<Rules> = LazyLock::new(|| {
+    Rules::from_toml_str(include_str!("../data/citify_rules.toml"))
+        .expect("Citify rules are valid.")
+});
+
+/// The `Rule` struct holds the conditions for matching an address, and the rewrites to apply to
+/// matching addresses.  Conditions and rewrites that are `None` do not apply.  See
+/// `data/default_rules.toml` for the file format.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct Rule {
+    /// The `name` field identifies the rule in the log.
+    pub name: String,
+    /// The `street_name` field matches the street name of the address.
+    #[serde(default)]
+    pub street_name: Option<String>,
+    /// The `directional` field matches the street name pre directional of the address.
+    #[serde(default)]
+    pub directional: Option<String>,
+    /// The `subaddress_id` field matches the subaddress identifier of the address.
+    #[serde(default)]
+    pub subaddress_id: Option<String>,
+    /// The `subaddress_id_prefix` field matches the beginning of the subaddress identifier.
+    #[serde(default)]
+    pub subaddress_id_prefix: Option<String>,
+    /// The `set_street_name` field holds the replacement street name.
+    #[serde(default)]
+    pub set_street_name: Option<String>,
+    /// The `set_street_type` field holds the replacement street name post type.
+    #[serde(default)]
+    pub set_street_type: Option<String>,
+    /// The `set_directional` field holds the replacement street name pre directional.
+    #[serde(default)]
+    pub set_directional: Option<String>,
+    /// The `set_subaddress_type` field holds the replacement subaddress type.
+    #[serde(default)]
+    pub set_subaddress_type: Option<String>,
+    /// The `set_subaddress_id` field holds the replacement subaddress identifier.
+    #[serde(default)]
+    pub set_subaddress_id: Option<String>,
+}
+
+impl Rule {
+    /// The `validate` method returns an error if a condition or rewrite of the rule holds a value
+    /// that is not valid for the component.
+    pub fn validate(&self) -> Result<(), Builder> {
+        let checks = [
+            (
+                AddressComponent::PreDirectional,
+                &self.directional,
+                Self::valid_directional as fn(&str) -> bool,
+            ),
+            (
+                AddressComponent::PreDirectional,
+                &self.set_directional,
+                Self::valid_directional,
+            ),
+            (
+                AddressComponent::PostType,
+                &self.set_street_type,
+                Self::valid_street_type,
+            ),
+            (
+                AddressComponent::SubaddressType,
+                &self.set_subaddress_type,
+                Self::valid_subaddress_type,
+            ),
+        ];
+        for (component, value, valid) in checks {
+            if let Some(value) = value {
+                if value != NULL && !valid(value) {
+                    return Err(Builder::new(
+                        format!("invalid {component} {value} in rule {}", self.name),
+                        "Rule".to_string(),
+                        line!(),
+                        file!().into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn valid_directional(value: &str) -> bool {
+        StreetNamePreDirectional::match_mixed(value).is_some()
+    }
+
+    fn valid_street_type(value: &str) -> bool {
+        StreetNamePostType::match_mixed(value).is_some()
+    }
+
+    fn valid_subaddress_type(value: &str) -> bool {
+        SubaddressType::match_mixed(value).is_some()
+    }
+
+    /// The `normalize` method converts a directional in `value` to the form read from addresses,
+    /// so that abbreviated directionals in conditions match.
+    fn normalize(component: AddressComponent, value: &str) -> String {
+        match component {
+            AddressComponent::PreDirectional => StreetNamePreDirectional::match_mixed(value)
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| value.to_string()),
+            _ => value.to_string(),
+        }
+    }
+
+    /// The `matches` method returns `true` if every condition of the rule matches `target`.
+    fn matches<T: RuleTarget + ?Sized>(&self, target: &T) -> bool {
+        let conditions = [
+            (AddressComponent::StreetName, &self.street_name),
+            (AddressComponent::PreDirectional, &self.directional),
+            (AddressComponent::SubaddressIdentifier, &self.subaddress_id),
+        ];
+        let matched = conditions.iter().all(|(component, condition)| {
+            match (condition, target.component(*component)) {
+                (None, _) => true,
+                (Some(condition), None) => condition == NULL,
+                (Some(condition), Some(value)) => {
+                    Self::normalize(*component, condition).eq_ignore_ascii_case(&value)
+                }
+            }
+        });
+        matched
+            && match &self.subaddress_id_prefix {
+                None => true,
+                Some(prefix) => target
+                    .component(AddressComponent::SubaddressIdentifier)
+                    .is_some_and(|id| id.to_uppercase().starts_with(&prefix.to_uppercase())),
+            }
+    }
+
+    /// The `apply` method rewrites the components of `target` if the rule matches, returning
+    /// `true` if the rule applied.
+    fn apply<T: RuleTarget + ?Sized>(&self, target: &mut T) -> bool {
+        if !self.matches(target) {
+            return false;
+        }
+        let label = target.rule_label();
+        if let Some(prefix) = &self.subaddress_id_prefix {
+            if self.set_subaddress_id.is_none() {
+                let id = target
+                    .component(AddressComponent::SubaddressIdentifier)
+                    .unwrap_or_default();
+                let remainder = id.get(prefix.len()..).unwrap_or_default().trim();
+                let remainder = (!remainder.is_empty()).then(|| remainder.to_string());
+                target.set_component(AddressComponent::SubaddressIdentifier, remainder);
+            }
+        }
+        let rewrites = [
+            (AddressComponent::StreetName, &self.set_street_name),
+            (AddressComponent::PostType, &self.set_street_type),
+            (AddressComponent::PreDirectional, &self.set_directional),
+            (AddressComponent::SubaddressType, &self.set_subaddress_type),
+            (
+                AddressComponent::SubaddressIdentifier,
+                &self.set_subaddress_id,
+            ),
+        ];
+        for (component, value) in rewrites {
+            if let Some(value) = value {
+                let value = (value != NULL).then(|| value.clone());
+                target.set_component(component, value);
+            }
+        }
+        tracing::trace!(
+            rule = %self.name,
+            address = %label,
+            "Applied standardization rule."
+        );
+        true
+    }
+}
+
+/// The `Rules` struct holds an ordered set of [`Rule`] values.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    Deref,
+    DerefMut,
+)]
+pub struct Rules(Vec<Rule>);
+
+/// The `RulesFile` struct is the layout of a TOML rule file, holding the rules in a `rule` array.
+#[derive(serde::Deserialize)]
+struct RulesFile {
+    rule: Vec<Rule>,
+}
+
+impl Rules {
+    /// The `new` method creates a rule set from `rules`, returning an error if any rule is
+    /// invalid.
+    pub fn new(rules: Vec<Rule>) -> Result<Self, Builder> {
+        for rule in &rules {
+            rule.validate()?;
+        }
+        Ok(Self(rules))
+    }
+
+    /// The `default_rules` method returns the default rule set, converting county address
+    /// naming conventions to city naming conventions.
+    pub fn default_rules() -> &'static Self {
+        &DEFAULT_RULES
+    }
+
+    /// The `citify_rules` method returns the rule set used by `citify`, converting county street
+    /// names to city street names in the quadrants where the city uses them.
+    pub fn citify_rules() -> &'static Self {
+        &CITIFY_RULES
+    }
+
+    /// The `from_path` method reads a rule set from the file at `path`, reading TOML if the file
+    /// has a `toml` extension and CSV otherwise.  The CSV columns use the field names of
+    /// [`Rule`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, AddressErrorKind> {
+        let path = path.as_ref();
+        let rules = if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"))
+        {
+            let text = std::fs::read_to_string(path)
+                .map_err(|source| Io::new(path.into(), source, line!(), file!().into()))?;
+            Self::from_toml_str(&text).map_err(|issue| {
+                let source = std::io::Error::new(std::io::ErrorKind::InvalidData, issue);
+                Io::new(path.into(), source, line!(), file!().into())
+            })?
+        } else {
+            Self(from_csv(path)?)
+        };
+        rules.iter().try_for_each(Rule::validate)?;
+        tracing::info!("{} rules read from {:?}.", rules.len(), path);
+        Ok(rules)
+    }
+
+    /// The `from_toml_str` method parses a rule set from the TOML in `text`.
+    fn from_toml_str(text: &str) -> Result<Self, String> {
+        let file = toml::from_str::<RulesFile>(text).map_err(|e| e.to_string())?;
+        Self::new(file.rule).map_err(|e| e.to_string())
+    }
+
+    /// The `apply` method applies each rule in order to `address`, returning the number of rules
+    /// applied.
+    pub fn apply<T: Address + ?Sized>(&self, address: &mut T) -> usize {
+        self.iter().filter(|rule| rule.apply(address)).count()
+    }
+
+    /// The `apply_partial` method applies each rule in order to the partial address `address`,
+    /// returning the number of rules applied.
+    pub fn apply_partial(&self, address: &mut PartialAddress) -> usize {
+        self.iter().filter(|rule| rule.apply(address)).count()
+    }
+}
+
+/// The `RuleTarget` trait reads and writes the components of an address that rules act upon, as
+/// strings.  Values are valid for the component, as checked by [`Rule::validate`].
+trait RuleTarget {
+    fn component(&self, component: AddressComponent) -> Option<String>;
+    fn set_component(&mut self, component: AddressComponent, value: Option<String>);
+    fn rule_label(&self) -> String;
+}
+
+impl<T: Address + ?Sized> RuleTarget for T {
+    fn component(&self, component: AddressComponent) -> Option<String> {
+        match component {
+            AddressComponent::StreetName => Some(self.street_name().clone()),
+            AddressComponent::PreDirectional => (*self.directional()).map(|d| d.to_string()),
+            AddressComponent::SubaddressIdentifier => self.subaddress_id().clone(),
+            _ => None,
+        }
+    }
+
+    fn set_component(&mut self, component: AddressComponent, value: Option<String>) {
+        match component {
+            AddressComponent::StreetName => *self.street_name_mut() = value.unwrap_or_default(),
+            AddressComponent::PostType => {
+                *self.street_type_mut() = value.and_then(|v| StreetNamePostType::match_mixed(&v))
+            }
+            AddressComponent::PreDirectional => {
+                *self.directional_mut() =
+                    value.and_then(|v| StreetNamePreDirectional::match_mixed(&v))
+            }
+            AddressComponent::SubaddressType => {
+                *self.subaddress_type_mut() = value.and_then(|v| SubaddressType::match_mixed(&v))
+            }
+            AddressComponent::SubaddressIdentifier => *self.subaddress_id_mut() = value,
+            _ => {}
+        }
+    }
+
+    fn rule_label(&self) -> String {
+        self.label()
+    }
+}
+
+impl RuleTarget for PartialAddress {
+    fn component(&self, component: AddressComponent) -> Option<String> {
+        match component {
+            AddressComponent::StreetName => self.street_name(),
+            AddressComponent::PreDirectional => {
+                self.street_name_pre_directional().map(|d| d.to_string())
+            }
+            AddressComponent::SubaddressIdentifier => self.subaddress_identifier(),
+            _ => None,
+        }
+    }
+
+    fn set_component(&mut self, component: AddressComponent, value: Option<String>) {
+        match component {
+            AddressComponent::StreetName => self.street_name = value,
+            AddressComponent::PostType => {
+                self.street_name_post_type = value.and_then(|v| StreetNamePostType::match_mixed(&v))
+            }
+            AddressComponent::PreDirectional => {
+                self.street_name_pre_directional =
+                    value.and_then(|v| StreetNamePreDirectional::match_mixed(&v))
+            }
+            AddressComponent::SubaddressType => {
+                self.subaddress_type = value.and_then(|v| SubaddressType::match_mixed(&v))
+            }
+            AddressComponent::SubaddressIdentifier => self.subaddress_identifier = value,
+            _ => {}
+        }
+    }
+
+    fn rule_label(&self) -> String {
+        self.label()
+    }
+}
@@ -0,0 +1,212 @@
+//! The `spatial` module contains a spatial index over projected address coordinates, used for
+//! reverse geocoding.
+use crate::{
+    Address, AddressErrorKind, Cartesian, Csv, Geographic, Io, Projection, SpatialAddress,
+    SpatialAddresses,
+};
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+use std::path::Path;
+
+/// The `IndexedPoint` type pairs projected coordinates with the position of the address in the
+/// indexed slice.
+type IndexedPoint = GeomWithData<[f64; 2], usize>;
+
+/// The `Neighbor` struct holds a reference to an address returned from a [`SpatialIndex`] query,
+/// and the distance from the query point to the address, in the units of the projected
+/// coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Neighbor<'a, T> {
+    /// The `address` field holds a reference to the indexed address.
+    pub address: &'a T,
+    /// The `distance` field holds the distance from the query point to the address.
+    pub distance: f64,
+}
+
+/// The `SpatialIndex` struct is an R-tree over the projected cartesian coordinates of a slice of
+/// addresses, supporting nearest neighbor and within-radius queries.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<'a, T> {
+    /// The `addresses` field holds a reference to the indexed addresses.
+    addresses: &'a [T],
+    /// The `tree` field holds the coordinates of `addresses`, tagged with their positions.
+    tree: RTree<IndexedPoint>,
+}
+
+impl<'a, T: Cartesian> SpatialIndex<'a, T> {
+    /// The `new` method builds the index over the addresses in `addresses`.
+    #[tracing::instrument(skip_all)]
+    pub fn new(addresses: &'a [T]) -> Self {
+        let points = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| IndexedPoint::new([address.x(), address.y()], i))
+            .collect::<Vec<IndexedPoint>>();
+        let tree = RTree::bulk_load(points);
+        tracing::trace!("Indexed {} addresses.", addresses.len());
+        Self { addresses, tree }
+    }
+
+    /// The `nearest` method returns the `k` addresses closest to the point at `x` and `y`,
+    /// ordered from nearest to farthest.
+    pub fn nearest(&self, x: f64, y: f64, k: usize) -> Vec<Neighbor<'a, T>> {
+        self.tree
+            .nearest_neighbor_iter_with_distance_2([x, y])
+            .take(k)
+            .map(|(point, distance)| self.neighbor(point, distance))
+            .collect()
+    }
+
+    /// The `within` method returns the addresses within `radius` of the point at `x` and `y`,
+    /// ordered from nearest to farthest.
+    pub fn within(&self, x: f64, y: f64, radius: f64) -> Vec<Neighbor<'a, T>> {
+        let mut neighbors = self
+            .tree
+            .locate_within_distance([x, y], radius.powi(2))
+            .map(|point| {
+                let distance = (point.geom()[0] - x).powi(2) + (point.geom()[1] - y).powi(2);
+                self.neighbor(point, distance)
+            })
+            .collect::<Vec<Neighbor<'a, T>>>();
+        neighbors.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        neighbors
+    }
+
+    /// The `neighbor` method converts a point from the tree and its squared distance from the
+    /// query point into a [`Neighbor`].
+    fn neighbor(&self, point: &IndexedPoint, distance_2: f64) -> Neighbor<'a, T> {
+        Neighbor {
+            address: &self.addresses[point.data],
+            distance: distance_2.sqrt(),
+        }
+    }
+
+    /// The `len` method returns the number of indexed addresses.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// The `is_empty` method returns `true` if the index holds no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.tree.size() == 0
+    }
+}
+
+impl SpatialAddresses {
+    /// The `nearest` method returns the `k` addresses in `self` closest to the point at `x` and
+    /// `y`, ordered from nearest to farthest.  The coordinates must use the same projection as
+    /// the addresses.  Builds a new [`SpatialIndex`] on each call, so prefer the index directly
+    /// when making many queries.
+    pub fn nearest(&self, x: f64, y: f64, k: usize) -> Vec<Neighbor<'_, SpatialAddress>> {
+        SpatialIndex::new(&self[..]).nearest(x, y, k)
+    }
+
+    /// The `nearest_csv` method reads the CSV file at `path`, reads coordinates in the coordinate
+    /// system `projection` from the fields named `x_column` and `y_column`, and looks up the
+    /// nearest addresses in `self`.  For GPS points, `x_column` and `y_column` name the longitude
+    /// and latitude columns, and `projection` is [`Projection::Wgs84`].  The points convert to
+    /// Web Mercator, the coordinate system of the address points, before the lookup, so `radius`
+    /// and the reported distances are in meters of Web Mercator.
+    /// When `radius` is set, returns every address within `radius` of the point, otherwise
+    /// returns the `k` nearest addresses.  Writes each input row to a CSV file at `output` once
+    /// per neighbor, with the fields `rank`, `label`, `distance`, `latitude`, `longitude`, `x` and
+    /// `y` added.  Rows with invalid coordinates or without neighbors are written once, with the
+    /// added fields empty.
+    #[allow(clippy::too_many_arguments)]
+    pub fn nearest_csv<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        path: P,
+        x_column: &str,
+        y_column: &str,
+        projection: Projection,
+        k: usize,
+        radius: Option<f64>,
+        output: Q,
+    ) -> Result<(), AddressErrorKind> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = csv::Reader::from_path(&path)
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        let headers = reader
+            .headers()
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?
+            .clone();
+        let position = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header == column)
+                .ok_or_else(|| {
+                    let source = std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("column {column} not found"),
+                    );
+                    Io::new(path.clone(), source, line!(), file!().into())
+                })
+        };
+        let x_position = position(x_column)?;
+        let y_position = position(y_column)?;
+
+        let index = SpatialIndex::new(&self[..]);
+        let output = output.as_ref().to_path_buf();
+        let mut writer = csv::Writer::from_path(&output)
+            .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+        let mut header = headers.clone();
+        header.extend([
+            "rank",
+            "label",
+            "distance",
+            "latitude",
+            "longitude",
+            "x",
+            "y",
+        ]);
+        writer
+            .write_record(&header)
+            .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+        let mut count = 0;
+        for result in reader.records() {
+            let row =
+                result.map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+            count += 1;
+            let x = row.get(x_position).unwrap_or_default().trim();
+            let y = row.get(y_position).unwrap_or_default().trim();
+            let neighbors = match (x.parse::<f64>(), y.parse::<f64>()) {
+                (Ok(x), Ok(y)) => {
+                    let (x, y) = projection.transform(Projection::WebMercator, x, y);
+                    match radius {
+                        Some(radius) => index.within(x, y, radius),
+                        None => index.nearest(x, y, k),
+                    }
+                }
+                _ => {
+                    tracing::warn!("Invalid coordinates: {x}, {y}");
+                    Vec::new()
+                }
+            };
+            if neighbors.is_empty() {
+                let mut row = row.clone();
+                row.extend(["", "", "", "", "", "", ""]);
+                writer
+                    .write_record(&row)
+                    .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+            }
+            for (rank, neighbor) in neighbors.iter().enumerate() {
+                let mut row = row.clone();
+                row.push_field(&(rank + 1).to_string());
+                row.push_field(&neighbor.address.label());
+                row.push_field(&neighbor.distance.to_string());
+                row.push_field(&neighbor.address.latitude().to_string());
+                row.push_field(&neighbor.address.longitude().to_string());
+                row.push_field(&neighbor.address.x().to_string());
+                row.push_field(&neighbor.address.y().to_string());
+                writer
+                    .write_record(&row)
+                    .map_err(|source| Csv::new(output.clone(), source, line!(), file!().into()))?;
+            }
+        }
+        tracing::info!("{} points read from {:?}.", count, &path);
+        writer
+            .flush()
+            .map_err(|source| Io::new(output.clone(), source, line!(), file!().into()))?;
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! The `utils` module contains utility functions accessed by multiple data types, where declaring
 //! a stand-alone function eliminates code duplication in different methods.
-use crate::{AddressError, AddressErrorKind, Bincode, Csv, Io};
+use crate::{AddressError, AddressErrorKind, Bincode, Csv, Io, Json};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 use serde::Serialize;
@@ -74,6 +74,130 @@ pub fn from_csv<T: DeserializeOwned + Clone, P: AsRef<std::path::Path>>(
     }
 }
 
+/// Generic function to serialize data types into a GeoJSON FeatureCollection.  Each item in
+/// `items` becomes a feature, with the fields of the item as properties, flattening nested
+/// structs.  The `coordinates` closure returns the (longitude, latitude) of the item in WGS84,
+/// or `None` to write a feature with a null geometry.
+pub fn to_geojson<T: Serialize, F: Fn(&T) -> Option<(f64, f64)>, P: AsRef<Path>>(
+    items: &[T],
+    coordinates: F,
+    path: P,
+) -> Result<(), AddressErrorKind> {
+    let path = path.as_ref().to_path_buf();
+    let mut features = Vec::with_capacity(items.len());
+    for item in items {
+        let value = serde_json::to_value(item)
+            .map_err(|source| Json::new(path.clone(), source, line!(), file!().into()))?;
+        let mut properties = serde_json::Map::new();
+        flatten_properties(value, &mut properties);
+        let geometry = match coordinates(item) {
+            Some((longitude, latitude)) => serde_json::json!({
+                "type": "Point",
+                "coordinates": [longitude, latitude],
+            }),
+            None => serde_json::Value::Null,
+        };
+        features.push(serde_json::json!({
+            "type": "Feature",
+            "geometry": geometry,
+            "properties": properties,
+        }));
+    }
+    let collection = serde_json::json!({
+        "type": "FeatureCollection",
+        "features": features,
+    });
+    let file = fs::File::create(&path)
+        .map_err(|source| Io::new(path.clone(), source, line!(), file!().into()))?;
+    serde_json::to_writer(std::io::BufWriter::new(file), &collection)
+        .map_err(|source| Json::new(path.clone(), source, line!(), file!().into()))?;
+    Ok(())
+}
+
+/// The `flatten_properties` function inserts the fields of `value` into `properties`, lifting
+/// the fields of nested objects to the top level.
+fn flatten_properties(
+    value: serde_json::Value,
+    properties: &mut serde_json::Map<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    serde_json::Value::Object(_) => flatten_properties(value, properties),
+                    _ => {
+                        properties.insert(key, value);
+                    }
+                }
+            }
+        }
+        _ => {
+            properties.insert("value".to_string(), value);
+        }
+    }
+}
+
+/// Generic function to deserialize data types from the features of a GeoJSON FeatureCollection.
+/// Reads each item from the properties of a feature.  For point features, the coordinates of the
+/// geometry fill the `longitude` and `latitude` properties, if absent.  Features that fail to
+/// deserialize are dropped with a warning reporting the count.
+pub fn from_geojson<T: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<T>, AddressErrorKind> {
+    let path = path.as_ref().to_path_buf();
+    let file = fs::File::open(&path)
+        .map_err(|source| Io::new(path.clone(), source, line!(), file!().into()))?;
+    let collection: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|source| Json::new(path.clone(), source, line!(), file!().into()))?;
+    let features = match collection.get("features") {
+        Some(serde_json::Value::Array(features)) => features,
+        _ => {
+            let source = std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "not a GeoJSON FeatureCollection",
+            );
+            return Err(Io::new(path, source, line!(), file!().into()).into());
+        }
+    };
+    let mut records = Vec::with_capacity(features.len());
+    let mut dropped = 0;
+    for feature in features {
+        let mut properties = match feature.get("properties") {
+            Some(serde_json::Value::Object(properties)) => properties.clone(),
+            _ => serde_json::Map::new(),
+        };
+        if let Some(serde_json::Value::Array(coordinates)) = feature
+            .get("geometry")
+            .filter(|geometry| geometry.get("type") == Some(&serde_json::json!("Point")))
+            .and_then(|geometry| geometry.get("coordinates"))
+        {
+            for (key, coordinate) in ["longitude", "latitude"].iter().zip(coordinates) {
+                properties
+                    .entry(key.to_string())
+                    .or_insert_with(|| coordinate.clone());
+            }
+        }
+        // Round trip through a string so that deserializers borrowing `&str` succeed.
+        let text = serde_json::Value::Object(properties).to_string();
+        match serde_json::from_str::<T>(&text) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                tracing::trace!("Dropping: {}", e.to_string());
+                dropped += 1;
+            }
+        }
+    }
+    if dropped > 0 {
+        tracing::warn!(
+            "{} of {} features in {:?} failed to deserialize and were dropped.",
+            dropped,
+            features.len(),
+            path
+        );
+    }
+    Ok(records)
+}
+
 /// The `save` method serializes the contents of self into binary and writes to a file at
 /// location `path`.  Errors bubble up from serialization in [`bincode`] or file system access during write.
 pub fn to_bin<T: Serialize, P: AsRef<Path>>(data: &T, path: P) -> Result<(), AddressError> {
@@ -137,6 +261,13 @@ pub trait IntoBin<T> {
     fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AddressError>;
 }
 
+/// The `IntoGeoJson` trait indicates the type can be written to a GeoJSON file.
+pub trait IntoGeoJson {
+    /// The `to_geojson` method attempts to serialize the data to a GeoJSON FeatureCollection at
+    /// location `path`.
+    fn to_geojson<P: AsRef<Path>>(&self, path: P) -> Result<(), AddressErrorKind>;
+}
+
 /// The `trace_init` function initializing the tracing subscriber.
 pub fn trace_init() {
     if tracing_subscriber::registry()
@@ -0,0 +1,365 @@
+//! The `validate` module checks address records against completeness rules drawn from the FGDC
+//! address standard and NENA requirements, reporting the malformed records.
+use crate::{
+    from_csv, to_csv, Address, AddressErrorKind, AddressPoint, AddressStatus, CommonAddress,
+    GeoAddress, GrantsPassAddress, GrantsPassSpatialAddress, IntoCsv, Io, JosephineCountyAddress,
+    JosephineCountyAddress2024, JosephineCountySpatialAddress, JosephineCountySpatialAddress2024,
+    PostalCommunity, SpatialAddress,
+};
+use derive_more::{Deref, DerefMut};
+use std::collections::{BTreeMap, HashMap};
+use strum::IntoEnumIterator;
+
+/// The `ValidationRule` enum designates the checks run by a [`Validator`].
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+    derive_more::FromStr,
+    strum::EnumIter,
+)]
+pub enum ValidationRule {
+    /// The zip code is not one of the [`PostalCommunity::zip_codes`] of the postal community.
+    UnknownZip,
+    /// The complete street name has neither a post type nor a pre type.  The FGDC standard does
+    /// not require a post type, but local addresses without one are rare and worth a look.
+    MissingPostType,
+    /// The subaddress type requires an identifier, and none is present.  See
+    /// [`crate::SubaddressType::requires_identifier`].
+    MissingSubaddressIdentifier,
+    /// A floor is set on a subaddress type outside a multi-story building.  See
+    /// [`crate::SubaddressType::has_floors`].
+    FloorOnSingleStory,
+    /// The address has had a [`AddressStatus::Temporary`] status for longer than the
+    /// [`Validator::temporary_days`] limit.
+    StaleTemporary,
+    /// The global ID of the record is shared with another record.
+    DuplicateGlobalId,
+}
+
+impl ValidationRule {
+    /// The `match_mixed` method attempts to match the string `input` against a variant of
+    /// `ValidationRule`, accepting the variant name in snake case or without separators.
+    #[tracing::instrument]
+    pub fn match_mixed(input: &str) -> Option<Self> {
+        match input.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+            "unknownzip" | "zip" => Some(Self::UnknownZip),
+            "missingposttype" | "posttype" => Some(Self::MissingPostType),
+            "missingsubaddressidentifier" | "subaddress" => Some(Self::MissingSubaddressIdentifier),
+            "flooronsinglestory" | "floor" => Some(Self::FloorOnSingleStory),
+            "staletemporary" | "temporary" => Some(Self::StaleTemporary),
+            "duplicateglobalid" | "globalid" => Some(Self::DuplicateGlobalId),
+            _ => None,
+        }
+    }
+}
+
+/// The `Validate` trait extends [`Address`] with the record metadata read by the
+/// [`ValidationRule::StaleTemporary`] and [`ValidationRule::DuplicateGlobalId`] rules.  Sources
+/// without the metadata use the default methods, and skip those rules.
+pub trait Validate: Address {
+    /// The `global_id` method returns the ESRI Global ID of the record, if any.
+    fn global_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// The `created_date` method returns the date of creation of the record, if any, as
+    /// formatted by the source.
+    fn created_date(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Validate for CommonAddress {}
+impl Validate for SpatialAddress {}
+impl Validate for GeoAddress {}
+impl Validate for AddressPoint {}
+impl Validate for JosephineCountyAddress {}
+impl Validate for JosephineCountySpatialAddress {}
+impl Validate for JosephineCountyAddress2024 {}
+impl Validate for JosephineCountySpatialAddress2024 {}
+
+impl Validate for GrantsPassAddress {
+    fn global_id(&self) -> Option<&str> {
+        Some(&self.global_id)
+    }
+
+    fn created_date(&self) -> Option<&str> {
+        self.created_date.as_deref()
+    }
+}
+
+impl Validate for GrantsPassSpatialAddress {
+    fn global_id(&self) -> Option<&str> {
+        Some(&self.global_id)
+    }
+
+    fn created_date(&self) -> Option<&str> {
+        self.created_date.as_deref()
+    }
+}
+
+/// The `Finding` struct records a record that fails a [`ValidationRule`].
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Finding {
+    /// The `record` field holds the position of the record in the validated records, starting at
+    /// zero.
+    pub record: usize,
+    /// The `label` field holds the address label of the record.
+    pub label: String,
+    /// The `global_id` field holds the global ID of the record, if any.
+    pub global_id: Option<String>,
+    /// The `rule` field holds the rule the record fails.
+    pub rule: ValidationRule,
+    /// The `message` field describes the failure.
+    pub message: String,
+}
+
+/// The `Findings` struct holds a vector of type [`Finding`], ordered by record.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    Deref,
+    DerefMut,
+    derive_new::new,
+)]
+pub struct Findings(Vec<Finding>);
+
+impl Findings {
+    /// The `by_record` method groups the findings by the position of the record.
+    pub fn by_record(&self) -> BTreeMap<usize, Vec<&Finding>> {
+        let mut records = BTreeMap::new();
+        for finding in self.iter() {
+            records
+                .entry(finding.record)
+                .or_insert_with(Vec::new)
+                .push(finding);
+        }
+        records
+    }
+
+    /// The `filter` method returns the findings for the rule `rule`.
+    pub fn filter(&self, rule: ValidationRule) -> Self {
+        Self(
+            self.iter()
+                .filter(|finding| finding.rule == rule)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl IntoCsv<Findings> for Findings {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+/// The `Validator` struct holds the rule set checked by [`Validator::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Validator {
+    /// The `rules` field holds the rules to check.
+    pub rules: Vec<ValidationRule>,
+    /// The `temporary_days` field holds the number of days an address may hold a temporary
+    /// status before failing [`ValidationRule::StaleTemporary`].
+    pub temporary_days: i64,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new(365)
+    }
+}
+
+impl Validator {
+    /// The `new` method creates a validator checking every rule, with a limit of
+    /// `temporary_days` on temporary addresses.
+    pub fn new(temporary_days: i64) -> Self {
+        Self {
+            rules: ValidationRule::iter().collect(),
+            temporary_days,
+        }
+    }
+
+    /// The `validate` method checks each record in `records` against the rules of `self`,
+    /// returning the findings ordered by record.
+    pub fn validate<T: Validate>(&self, records: &[T]) -> Findings {
+        let today = today();
+        let mut global_ids = HashMap::new();
+        if self.rules.contains(&ValidationRule::DuplicateGlobalId) {
+            for record in records {
+                if let Some(id) = record.global_id().and_then(normalize_global_id) {
+                    *global_ids.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut findings = Vec::new();
+        for (position, record) in records.iter().enumerate() {
+            let global_id = record.global_id().map(|id| id.to_string());
+            for (rule, message) in self.check(record, today, &global_ids) {
+                findings.push(Finding {
+                    record: position,
+                    label: record.label(),
+                    global_id: global_id.clone(),
+                    rule,
+                    message,
+                });
+            }
+        }
+        tracing::info!(
+            "{} findings for {} of {} records.",
+            findings.len(),
+            findings
+                .iter()
+                .map(|finding| finding.record)
+                .collect::<std::collections::HashSet<usize>>()
+                .len(),
+            records.len()
+        );
+        Findings(findings)
+    }
+
+    /// The `check` method returns the rules failed by `record` with a message for each, where
+    /// `today` is the current date in days since the Unix epoch, and `global_ids` counts the
+    /// records holding each normalized global ID.
+    fn check<T: Validate>(
+        &self,
+        record: &T,
+        today: i64,
+        global_ids: &HashMap<String, usize>,
+    ) -> Vec<(ValidationRule, String)> {
+        let mut failures = Vec::new();
+        for rule in &self.rules {
+            let message = match rule {
+                ValidationRule::UnknownZip => {
+                    PostalCommunity::match_mixed(record.postal_community()).and_then(|community| {
+                        (!community.zip_codes().contains(&record.zip())).then(|| {
+                            format!(
+                                "zip {} is not a zip code of {}",
+                                record.zip(),
+                                community.label()
+                            )
+                        })
+                    })
+                }
+                ValidationRule::MissingPostType => (record.street_type().is_none()
+                    && record.street_name_pre_type().is_none())
+                .then(|| format!("no post type for {}", record.complete_street_name(true))),
+                ValidationRule::MissingSubaddressIdentifier => record
+                    .subaddress_type()
+                    .filter(|kind| kind.requires_identifier() && record.subaddress_id().is_none())
+                    .map(|kind| format!("subaddress type {kind} has no identifier")),
+                ValidationRule::FloorOnSingleStory => {
+                    match (record.floor(), record.subaddress_type()) {
+                        (Some(floor), Some(kind)) if !kind.has_floors() => {
+                            Some(format!("floor {floor} set on subaddress type {kind}"))
+                        }
+                        _ => None,
+                    }
+                }
+                ValidationRule::StaleTemporary => match record.status() {
+                    AddressStatus::Temporary => record
+                        .created_date()
+                        .and_then(|date| match parse_date(date) {
+                            Some(days) => Some((date, today - days)),
+                            None => {
+                                tracing::warn!(
+                                    "Could not read created date {date} of {}.",
+                                    record.label()
+                                );
+                                None
+                            }
+                        })
+                        .filter(|(_, age)| *age > self.temporary_days)
+                        .map(|(date, age)| format!("temporary since {date}, {age} days ago")),
+                    _ => None,
+                },
+                ValidationRule::DuplicateGlobalId => record
+                    .global_id()
+                    .and_then(normalize_global_id)
+                    .and_then(|id| {
+                        let count = global_ids.get(&id).copied().unwrap_or_default();
+                        (count > 1).then(|| format!("global id {id} held by {count} records"))
+                    }),
+            };
+            if let Some(message) = message {
+                failures.push((*rule, message));
+            }
+        }
+        failures
+    }
+}
+
+/// The `normalize_global_id` function returns the global ID `id` in upper case without braces,
+/// or `None` if the ID is empty.
+pub(crate) fn normalize_global_id(id: &str) -> Option<String> {
+    let id = id.trim().trim_start_matches('{').trim_end_matches('}');
+    (!id.is_empty()).then(|| id.to_uppercase())
+}
+
+/// The `today` function returns the current date in days since the Unix epoch.
+fn today() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| (elapsed.as_secs() / 86_400) as i64)
+        .unwrap_or_default()
+}
+
+/// The `parse_date` function reads the date at the start of `text` in days since the Unix epoch.
+/// Reads the dates written by ArcGIS exports, as "2024-05-20", "2024/05/20" or "5/20/2024",
+/// optionally followed by a time, or as milliseconds since the epoch.
+fn parse_date(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if text.len() >= 10 && text.chars().all(|c| c.is_ascii_digit()) {
+        return text.parse::<i64>().ok().map(|ms| ms.div_euclid(86_400_000));
+    }
+    let date = text.split([' ', 'T']).next()?;
+    let parts = date
+        .split(['-', '/'])
+        .map(|part| part.parse::<i64>().ok())
+        .collect::<Option<Vec<i64>>>()?;
+    let [a, b, c] = parts[..] else {
+        return None;
+    };
+    let (year, month, day) = match date.find(['-', '/']) {
+        Some(4) => (a, b, c),
+        _ => (c, a, b),
+    };
+    ((1..=12).contains(&month) && (1..=31).contains(&day))
+        .then(|| days_from_civil(year, month, day))
+}
+
+/// The `days_from_civil` function returns the number of days from the Unix epoch to the date
+/// `year`, `month` and `day` in the proleptic Gregorian calendar, following Howard Hinnant,
+/// "chrono-Compatible Low-Level Date Algorithms".
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
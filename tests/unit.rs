@@ -1,9 +1,15 @@
 use destination::{
-    from_csv, Address, Addresses, BusinessLicenses, BusinessMatchRecords, Businesses,
-    FireInspectionMatchRecords, FireInspections, GeoAddresses, GrantsPassAddresses,
-    GrantsPassSpatialAddresses, IntoBin, IntoCsv, Io, JosephineCountyAddresses2024, MatchRecords,
-    Nom, Parse, PartialAddress, PostalCommunity, SpatialAddresses, StreetNamePostType,
-    StreetNamePreDirectional, SubaddressType,
+    from_csv, Address, AddressComponent, AddressIndex, AddressNumberRange, Addresses, Ambiguity,
+    BusinessLicenses, BusinessMatchRecords, Businesses, Cartesian, CommonAddress, ComponentPolicy,
+    Confidence, FireInspectionMatchRecords, FireInspections, GeoAddresses, Geographic,
+    GrantsPassAddress, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin, IntoCsv,
+    IntoGeoJson, Io, JosephineCountyAddresses2024, MailingLabel, MailingLabels, MatchAssignment,
+    MatchPartialRecord, MatchPartialRecords, MatchPolicy, MatchRecord, MatchRecords, MatchStatus,
+    NenaSiteAddressPoints, NguidGenerator, Nom, OrdinalStyle, Parse, PartialAddress,
+    PartialAddresses, Point, PostalCommunity, PostalDelivery, PostalDeliveryType, Projection,
+    ReconcileRecords, ReconcileStatus, Rules, SpatialAddress, SpatialAddresses,
+    SpatialAddressesRaw, SpatialIndex, StreetNamePostType, StreetNamePreDirectional,
+    StreetNamePreType, SubaddressType, ValidationRule, Validator,
 };
 use test_log::test;
 use tracing::{info, trace};
@@ -94,7 +100,11 @@ fn match_city_address() -> anyhow::Result<()> {
     let county_addresses = SpatialAddresses::load(county_path)?;
     assert_eq!(county_addresses.len(), 45564);
     info!("Matching single address.");
-    let match_records = MatchRecords::new(&city_addresses[0].clone(), &county_addresses);
+    let match_records = MatchRecords::new(
+        &city_addresses[0].clone(),
+        &county_addresses,
+        &MatchPolicy::default(),
+    );
     info!("Record 0 is: {:?}", match_records[0]);
     Ok(())
 }
@@ -143,7 +153,11 @@ fn match_city_addresses() -> anyhow::Result<()> {
     let county_path = "data/county_addresses.data";
     let city_addresses = SpatialAddresses::load(city_path)?;
     let county_addresses = SpatialAddresses::load(county_path)?;
-    let match_records = MatchRecords::compare(&city_addresses[0..10], &county_addresses);
+    let match_records = MatchRecords::compare(
+        &city_addresses[0..10],
+        &county_addresses,
+        &MatchPolicy::default(),
+    );
     assert_eq!(match_records.len(), 10);
     Ok(())
 }
@@ -154,7 +168,11 @@ fn filter_status() -> anyhow::Result<()> {
     let county_path = "data/county_addresses.data";
     let city_addresses = SpatialAddresses::load(city_path)?;
     let county_addresses = SpatialAddresses::load(county_path)?;
-    let match_records = MatchRecords::compare(&city_addresses[0..1000], &county_addresses);
+    let match_records = MatchRecords::compare(
+        &city_addresses[0..1000],
+        &county_addresses,
+        &MatchPolicy::default(),
+    );
     assert_eq!(match_records.len(), 1000);
     let filtered = match_records.clone().filter("status");
     assert_eq!(filtered.len(), 965);
@@ -168,7 +186,11 @@ fn filter_missing() -> anyhow::Result<()> {
     let county_path = "data/county_addresses.data";
     let city_addresses = SpatialAddresses::load(city_path)?;
     let county_addresses = SpatialAddresses::load(county_path)?;
-    let match_records = MatchRecords::compare(&city_addresses[0..1000], &county_addresses);
+    let match_records = MatchRecords::compare(
+        &city_addresses[0..1000],
+        &county_addresses,
+        &MatchPolicy::default(),
+    );
     assert_eq!(match_records.len(), 1000);
     let filtered = match_records.clone().filter("missing");
     assert_eq!(filtered.len(), 0);
@@ -176,6 +198,75 @@ fn filter_missing() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Checks that indexed comparison returns the same records as scanning the full target set.
+#[test]
+fn indexed_compare() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = &city_addresses[0..500];
+    let indexed = MatchRecords::compare(source, &city_addresses, &MatchPolicy::default());
+    let mut scanned = Vec::new();
+    for address in source {
+        scanned.append(&mut MatchRecords::new(
+            address,
+            &city_addresses,
+            &MatchPolicy::default(),
+        ));
+    }
+    assert_eq!(indexed.len(), scanned.len());
+    for (a, b) in indexed.iter().zip(scanned.iter()) {
+        assert_eq!(a.match_status, b.match_status);
+        assert_eq!(a.address_label, b.address_label);
+        assert_eq!(a.subaddress_type, b.subaddress_type);
+        assert_eq!(a.status, b.status);
+    }
+    info!("Indexed address comparison matches linear scan.");
+
+    let business_path = "data/active_business_licenses.csv";
+    let businesses = BusinessLicenses::from_csv(business_path)?;
+    let indexed = BusinessMatchRecords::compare(&businesses, &city_addresses);
+    let mut scanned = Vec::new();
+    for business in businesses.iter() {
+        scanned.append(&mut BusinessMatchRecords::new(business, &city_addresses));
+    }
+    assert_eq!(indexed.to_vec(), scanned);
+    info!("Indexed business comparison matches linear scan.");
+    Ok(())
+}
+
+// Checks that fuzzy comparison pairs an address with a misspelled street name.
+#[test]
+fn fuzzy_compare() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let original = city_addresses
+        .iter()
+        .find(|a| a.street_name().len() > 6)
+        .unwrap();
+    let mut misspelled = original.clone();
+    let mut street_name = original.street_name().clone();
+    street_name.pop();
+    *misspelled.street_name_mut() = street_name;
+    let source = vec![misspelled];
+    let exact = MatchRecords::compare(&source, &city_addresses, &MatchPolicy::default());
+    assert_eq!(exact[0].match_status, MatchStatus::Missing);
+    let fuzzy = MatchRecords::compare_fuzzy(&source, &city_addresses, 0.8, &MatchPolicy::default());
+    assert_eq!(fuzzy.len(), 1);
+    assert_eq!(fuzzy[0].match_status, MatchStatus::Probable);
+    assert!(fuzzy[0].similarity.unwrap() >= 0.8);
+    assert!(fuzzy[0]
+        .other_label
+        .as_ref()
+        .unwrap()
+        .contains(original.street_name().as_str()));
+    let strict =
+        MatchRecords::compare_fuzzy(&source, &city_addresses, 1.0, &MatchPolicy::default());
+    assert_eq!(strict[0].match_status, MatchStatus::Missing);
+    assert!(strict[0].other_label.is_some());
+    info!("Fuzzy comparison pairs misspelled street names.");
+    Ok(())
+}
+
 #[test]
 fn address_number_parser() {
     let a1 = "1 FIRE MOUNTAIN WAY, Grants Pass";
@@ -216,6 +307,103 @@ fn address_number_suffix_parser() {
     assert_eq!(Parse::address_number_suffix(a4), Ok(("LEWIS AVE", None)));
 }
 
+#[test]
+fn address_number_ranges() -> anyhow::Result<()> {
+    assert_eq!(
+        Parse::address_number_range("-110 SE 6TH ST", 100),
+        Ok((" SE 6TH ST", Some(110)))
+    );
+    assert_eq!(
+        Parse::address_number_range(" - 110 SE 6TH ST", 100),
+        Ok((" SE 6TH ST", Some(110)))
+    );
+    assert_eq!(
+        Parse::address_number_range("-1/2 MAIN ST", 12),
+        Ok(("-1/2 MAIN ST", None))
+    );
+    assert_eq!(
+        Parse::address_number_suffix("-1/2 MAIN ST"),
+        Ok((" MAIN ST", Some("1/2")))
+    );
+    assert_eq!(
+        Parse::address_number_suffix(" ½ MAIN ST"),
+        Ok((" MAIN ST", Some("1/2")))
+    );
+    assert_eq!(
+        Parse::address_number_suffix(" O'BRIEN ST"),
+        Ok(("O'BRIEN ST", None))
+    );
+
+    let (_, range) = Parse::address("100-110 SE 6TH ST")?;
+    assert_eq!(range.address_number(), Some(100));
+    assert_eq!(range.address_number_to(), Some(110));
+    assert_eq!(range.label(), "100-110 SE 6TH ST");
+    let numbers = range.address_numbers();
+    assert_eq!(numbers, vec![100, 102, 104, 106, 108, 110]);
+    assert_eq!(
+        Parse::address_number_range("-3 MAIN ST", 1205),
+        Ok(("-3 MAIN ST", None))
+    );
+    let (_, descending) = Parse::address("1205-3 MAIN ST")?;
+    assert_eq!(descending.address_number(), Some(1205));
+    assert_eq!(descending.address_number_to(), None);
+    assert_eq!(descending.address_number_range(), None);
+    assert_eq!(AddressNumberRange::new(1205, 3), None);
+    let (_, wide) = Parse::address("1-99999 MAIN ST")?;
+    let wide_range = wide.address_number_range().expect("range");
+    assert!(wide_range.contains(50001));
+    assert!(wide_range.numbers().is_none());
+    assert_eq!(wide.address_numbers(), vec![1]);
+    let (_, fraction) = Parse::address("12-1/2 MAIN ST")?;
+    assert_eq!(fraction.address_number(), Some(12));
+    assert_eq!(fraction.address_number_to(), None);
+    assert_eq!(fraction.address_number_suffix(), Some("1/2".to_string()));
+    assert_eq!(fraction.label(), "12 1/2 MAIN ST");
+
+    // A range matches every address on the street within the range.
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let street = |a: &SpatialAddress| a.label()[a.number().to_string().len()..].to_string();
+    let plain = |a: &&SpatialAddress| a.subaddress_id().is_none() && a.number_suffix().is_none();
+    let address = city_addresses
+        .iter()
+        .filter(plain)
+        .find(|a| {
+            city_addresses.iter().filter(plain).any(|b| {
+                let offset = b.number() - a.number();
+                (2..=40).contains(&offset) && offset % 2 == 0 && street(b) == street(a)
+            })
+        })
+        .unwrap();
+    let number = address.number();
+    let label = address.label();
+    let label = format!(
+        "{}-{}{}",
+        number,
+        number + 40,
+        &label[number.to_string().len()..]
+    );
+    let (_, partial) = Parse::address(&label)
+        .map_err(|source| Nom::new(label.clone(), source, line!(), file!().to_string()))?;
+    let records = MatchPartialRecord::compare(&partial, &city_addresses);
+    assert!(records
+        .iter()
+        .all(|r| r.match_status() == MatchStatus::Matching));
+    assert!(records
+        .iter()
+        .any(|r| r.other_label() == Some(address.label())));
+    let hits = city_addresses
+        .iter()
+        .filter(|a| {
+            MatchPartialRecord::coincident(&partial, *a)
+                .is_some_and(|r| r.match_status() == MatchStatus::Matching)
+        })
+        .count();
+    assert!(hits > 1);
+    assert_eq!(records.len(), hits);
+    info!("{} matches {} addresses.", label, hits);
+    Ok(())
+}
+
 #[test]
 fn pre_directional_parser() {
     let a1 = "NW 6TH ST";
@@ -232,6 +420,46 @@ fn pre_directional_parser() {
     );
 }
 
+#[test]
+fn post_directional_parser() -> anyhow::Result<()> {
+    assert_eq!(
+        Parse::post_directional(" NW, Grants Pass"),
+        Ok((", Grants Pass", Some(StreetNamePreDirectional::NORTHWEST)))
+    );
+    assert_eq!(
+        Parse::post_directional(", Grants Pass"),
+        Ok((", Grants Pass", None))
+    );
+    assert_eq!(Parse::post_directional(" SUITE A"), Ok((" SUITE A", None)));
+
+    let mut comp = PartialAddress::default();
+    comp.set_address_number(100);
+    comp.set_street_name("MAIN");
+    comp.set_post_type(&StreetNamePostType::STREET);
+    comp.set_post_directional(&StreetNamePreDirectional::NORTHWEST);
+    comp.set_subaddress_type(&SubaddressType::Suite);
+    comp.set_subaddress_identifier("A");
+    comp.postal_community = Some(PostalCommunity::GrantsPass);
+    let (_, parsed) = Parse::address("100 MAIN ST NW SUITE A, Grants Pass")?;
+    assert_eq!(parsed, comp);
+    assert_eq!(parsed.label(), "100 MAIN ST NW STE A");
+
+    let mut address = CommonAddress {
+        number: 100,
+        street_name: "MAIN".to_string(),
+        street_type: Some(StreetNamePostType::STREET),
+        post_directional: Some(StreetNamePreDirectional::NORTHWEST),
+        ..Default::default()
+    };
+    assert_eq!(address.label(), "100 MAIN ST NW");
+    assert_eq!(address.complete_street_name(false), "MAIN STREET NORTHWEST");
+    let other = address.clone();
+    assert!(address.coincident(&other).coincident);
+    *address.street_name_post_directional_mut() = None;
+    assert!(!address.coincident(&other).coincident);
+    Ok(())
+}
+
 #[test]
 fn street_type_parser() {
     let a1 = " WAY, Grants Pass";
@@ -275,6 +503,49 @@ fn multi_word_parser() {
     );
 }
 
+#[test]
+fn street_name_backtracking() -> anyhow::Result<()> {
+    assert_eq!(
+        Parse::street_name(" PARK PLAZA DRIVE"),
+        Ok(("DRIVE", Some("PARK PLAZA".to_string())))
+    );
+    assert_eq!(
+        Parse::street_name(" PARK VIEW PLAZA CIRCLE DR STE 4"),
+        Ok(("DR STE 4", Some("PARK VIEW PLAZA CIRCLE".to_string())))
+    );
+
+    let cases = [
+        ("100 WEST STREET", None, "WEST", StreetNamePostType::STREET),
+        ("100 NORTH ST", None, "NORTH", StreetNamePostType::STREET),
+        ("100 EAST AVE", None, "EAST", StreetNamePostType::AVENUE),
+        (
+            "100 PARK PLAZA DRIVE",
+            None,
+            "PARK PLAZA",
+            StreetNamePostType::DRIVE,
+        ),
+        (
+            "100 N PARK PLAZA DR",
+            Some(StreetNamePreDirectional::NORTH),
+            "PARK PLAZA",
+            StreetNamePostType::DRIVE,
+        ),
+        (
+            "100 WEST PARK PLAZA LOOP",
+            Some(StreetNamePreDirectional::WEST),
+            "PARK PLAZA",
+            StreetNamePostType::LOOP,
+        ),
+    ];
+    for (input, directional, street_name, post_type) in cases {
+        let (_, parsed) = Parse::address(input)?;
+        assert_eq!(parsed.street_name_pre_directional(), directional);
+        assert_eq!(parsed.street_name(), Some(street_name.to_string()));
+        assert_eq!(parsed.street_name_post_type(), Some(post_type));
+    }
+    Ok(())
+}
+
 #[test]
 fn subaddress_type_parser() {
     let a1 = " STE A";
@@ -348,6 +619,454 @@ fn subaddress_identifiers_parser() {
     );
 }
 
+#[test]
+fn subaddress_chain_parser() -> anyhow::Result<()> {
+    let (_, chain) = Parse::address("123 NE 6TH ST BLDG 3 APT 12, GRANTS PASS")?;
+    assert_eq!(chain.building(), Some("3".to_string()));
+    assert_eq!(chain.subaddress_type(), Some(SubaddressType::Apartment));
+    assert_eq!(chain.subaddress_identifier(), Some("12".to_string()));
+    assert_eq!(chain.postal_community, Some(PostalCommunity::GrantsPass));
+    assert_eq!(chain.label(), "123 NE 6TH ST BLDG 3 APT 12");
+
+    let (_, trailing) = Parse::address("123 NE 6TH ST APT 12 BLDG 3")?;
+    assert_eq!(trailing.building(), Some("3".to_string()));
+    assert_eq!(trailing.subaddress_identifier(), Some("12".to_string()));
+
+    let (_, floor) = Parse::address("123 NE 6TH ST BLDG A FL 2 STE 200")?;
+    assert_eq!(floor.building(), Some("A".to_string()));
+    assert_eq!(floor.floor(), Some(2));
+    assert_eq!(floor.subaddress_type(), Some(SubaddressType::Suite));
+    assert_eq!(floor.subaddress_identifier(), Some("200".to_string()));
+
+    let (_, range) = Parse::address("123 NE 6TH ST STE 100-104")?;
+    assert_eq!(range.subaddress_identifier(), Some("100-104".to_string()));
+    assert_eq!(
+        range.subaddress_identifiers(),
+        vec!["100", "101", "102", "103", "104"]
+    );
+    let (_, letters) = Parse::address("123 NE 6TH ST UNIT A - C")?;
+    assert_eq!(letters.subaddress_identifiers(), vec!["A", "B", "C"]);
+
+    // A unit range matches each unit in the range.
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let unit = |a: &SpatialAddress| {
+        a.subaddress_id()
+            .as_ref()
+            .and_then(|id| id.parse::<u64>().ok())
+    };
+    let (first, second) = city_addresses
+        .iter()
+        .filter(|a| unit(a).is_some())
+        .find_map(|a| {
+            city_addresses
+                .iter()
+                .find(|b| {
+                    b.number() == a.number()
+                        && b.street_name() == a.street_name()
+                        && b.directional() == a.directional()
+                        && b.subaddress_type() == a.subaddress_type()
+                        && b.building() == a.building()
+                        && unit(b)
+                            .is_some_and(|u| u > unit(a).unwrap() && u < unit(a).unwrap() + 10)
+                })
+                .map(|b| (a, b))
+        })
+        .unwrap();
+    let mut partial = PartialAddress::default();
+    partial.set_address_number(first.number());
+    partial.street_name_pre_directional = *first.directional();
+    partial.set_street_name(first.street_name());
+    partial.street_name_post_type = *first.street_type();
+    partial.subaddress_type = *first.subaddress_type();
+    partial.building = first.building().clone();
+    partial.set_subaddress_identifier(&format!(
+        "{}-{}",
+        unit(first).unwrap(),
+        unit(second).unwrap()
+    ));
+    let records = MatchPartialRecord::compare(&partial, &city_addresses);
+    assert!(records.len() > 1);
+    assert!(records
+        .iter()
+        .all(|r| r.match_status() == MatchStatus::Matching));
+    assert!(records
+        .iter()
+        .any(|r| r.other_label() == Some(second.label())));
+    info!("{} matches {} units.", partial.label(), records.len());
+    Ok(())
+}
+
+#[test]
+fn postal_delivery_parser() -> anyhow::Result<()> {
+    let (_, po_box) = Parse::address("P.O. Box 1234, Grants Pass, OR 97526")?;
+    assert_eq!(
+        po_box.postal_delivery_type(),
+        Some(PostalDeliveryType::PoBox)
+    );
+    assert_eq!(po_box.postal_delivery_box, Some("1234".to_string()));
+    assert_eq!(po_box.street_name(), None);
+    assert_eq!(po_box.postal_community, Some(PostalCommunity::GrantsPass));
+    assert_eq!(po_box.zip_code, Some(97526));
+    assert_eq!(po_box.label(), "PO BOX 1234");
+    assert!(!po_box.is_physical());
+
+    let (_, rural) = Parse::address("RR 2 BOX 152")?;
+    assert_eq!(
+        rural.postal_delivery(),
+        Some(PostalDelivery {
+            delivery_type: PostalDeliveryType::RuralRoute,
+            route: Some("2".to_string()),
+            box_id: Some("152".to_string()),
+        })
+    );
+    let (_, contract) = Parse::address("HCR 68 #23A")?;
+    assert_eq!(contract.label(), "HC 68 BOX 23A");
+    let (_, general) = Parse::address("GENERAL DELIVERY, MERLIN")?;
+    assert_eq!(
+        general.postal_delivery_type(),
+        Some(PostalDeliveryType::GeneralDelivery)
+    );
+    assert_eq!(general.postal_community, Some(PostalCommunity::Merlin));
+    assert_eq!(
+        Parse::postal_delivery("BOX CANYON RD"),
+        Ok(("BOX CANYON RD", None))
+    );
+    let (_, street) = Parse::address("100 BOX CANYON RD")?;
+    assert!(street.is_physical());
+
+    // Mailing addresses take a distinct match status instead of missing.
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let records = MatchPartialRecord::compare(&po_box, &city_addresses);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].match_status(), MatchStatus::NonPhysical);
+    let records = MatchPartialRecords::compare(
+        &PartialAddresses::new(vec![po_box, street]),
+        &city_addresses,
+    );
+    assert_eq!(records.clone().filter("nonphysical").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn highway_aliases() -> anyhow::Result<()> {
+    let aliases = [
+        ("100 HWY 199", StreetNamePreType::Highway),
+        ("100 US HIGHWAY 199", StreetNamePreType::UsHighway),
+        (
+            "100 U.S. Hwy 199, GRANTS PASS",
+            StreetNamePreType::UsHighway,
+        ),
+        ("100 US-199", StreetNamePreType::UsHighway),
+        ("100 OR-238", StreetNamePreType::StateHighway),
+        ("100 STATE ROUTE 238", StreetNamePreType::StateRoute),
+        ("100 INTERSTATE 5", StreetNamePreType::Interstate),
+        ("100 I-5", StreetNamePreType::Interstate),
+    ];
+    for (label, pre_type) in aliases {
+        let (_, address) = Parse::address(label)?;
+        assert_eq!(address.pre_type(), Some(pre_type), "{label}");
+        assert!(address
+            .street_name()
+            .is_some_and(|name| name == "199" || name == "238" || name == "5"));
+    }
+    let (_, named) = Parse::address("100 REDWOOD HWY")?;
+    assert_eq!(named.pre_type(), None);
+    assert_eq!(named.street_name(), Some("REDWOOD".to_string()));
+    assert_eq!(
+        named.street_name_post_type(),
+        Some(StreetNamePostType::HIGHWAY)
+    );
+    let (_, initials) = Parse::address("100 I ST")?;
+    assert_eq!(initials.pre_type(), None);
+    assert_eq!(initials.street_name(), Some("I".to_string()));
+    assert_eq!(
+        StreetNamePreType::match_mixed("U.S. Hwy"),
+        Some(StreetNamePreType::UsHighway)
+    );
+
+    // Different spellings of a route refer to the same street.
+    use StreetNamePreType as P;
+    assert!(P::same_street(
+        (Some(P::Highway), "199"),
+        (Some(P::UsHighway), "199")
+    ));
+    assert!(P::same_street(
+        (None, "HIGHWAY 199"),
+        (Some(P::UsHighway), "199")
+    ));
+    assert!(P::same_street(
+        (None, "OR-238"),
+        (Some(P::StateRoute), "238")
+    ));
+    assert!(!P::same_street(
+        (Some(P::UsHighway), "199"),
+        (Some(P::StateHighway), "199")
+    ));
+    assert!(!P::same_street(
+        (Some(P::UsHighway), "199"),
+        (Some(P::UsHighway), "99")
+    ));
+
+    let county = CommonAddress {
+        number: 100,
+        street_name: "HIGHWAY 199".to_string(),
+        ..Default::default()
+    };
+    let city = CommonAddress {
+        number: 100,
+        pre_type: Some(StreetNamePreType::UsHighway),
+        street_name: "199".to_string(),
+        ..Default::default()
+    };
+    assert!(county.coincident(&city).coincident);
+    let cities = [city];
+    let index = AddressIndex::new(&cities);
+    assert_eq!(index.candidates(100, None, "HWY 199").len(), 1);
+
+    // Business licenses hold the pre type in the street name.
+    let license_path = std::env::temp_dir().join(format!("licenses_{}.csv", std::process::id()));
+    let header = "CompanyName,ContactName,Location,CodeNumber,BusinessType,dba,OpenedDate,\
+        BLGLOBALENTITYEXTENSIONID,BusinessPhone,ADDRESSLINE1,ADDRESSLINE2,ADDRESSLINE3,\
+        PREDIRECTION,STREETTYPE,POSTDIRECTION,UNITORSUITE,CITY,STATE,POSTALCODE,ISSUEDDATE,\
+        LICENSENUMBER,EXPIRATIONDATE";
+    let row =
+        |street: &str| format!(",,,1,Retail,,,,,100,{street},,,,,,GRANTS PASS,OR,97526,,BTAX-1,");
+    std::fs::write(
+        &license_path,
+        format!("{header}\n{}\n{}\n", row("MT BAKER"), row("BAKER")),
+    )?;
+    let licenses = BusinessLicenses::from_csv(&license_path)?;
+    let mount = SpatialAddress {
+        address: CommonAddress {
+            number: 100,
+            pre_type: Some(StreetNamePreType::Mount),
+            street_name: "BAKER".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(licenses[0].coincident(&mount).is_some());
+    assert!(licenses[1].coincident(&mount).is_none());
+    // The indexed comparison agrees with the linear one on pre-typed streets.
+    let targets = [mount];
+    let linear = licenses
+        .iter()
+        .flat_map(|license| BusinessMatchRecords::new(license, &targets).to_vec())
+        .collect::<Vec<_>>();
+    let indexed = BusinessMatchRecords::compare(&licenses, &targets);
+    assert_eq!(*indexed, linear);
+    assert_eq!(indexed.filter("matching").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn ordinal_street_names() -> anyhow::Result<()> {
+    for label in ["100 SIXTH ST", "100 6TH ST", "100 6 ST", "100 6th St"] {
+        let (_, address) = Parse::address(label)?;
+        assert_eq!(address.street_name(), Some("6TH".to_string()), "{label}");
+    }
+    let (_, compound) = Parse::address("100 TWENTY-FIRST AVE")?;
+    assert_eq!(compound.street_name(), Some("21ST".to_string()));
+    // Route numbers are not ordinals.
+    let (_, highway) = Parse::address("100 HWY 199")?;
+    assert_eq!(highway.street_name(), Some("199".to_string()));
+
+    assert_eq!(OrdinalStyle::canonical("TWENTY FIRST"), "21ST");
+    assert_eq!(OrdinalStyle::canonical("11ST"), "11TH");
+    assert_eq!(OrdinalStyle::canonical("FIRSTHAND"), "FIRSTHAND");
+    assert_eq!(OrdinalStyle::Spelled.format("42ND"), "FORTY-SECOND");
+    assert_eq!(OrdinalStyle::Spelled.format("112TH"), "112TH");
+    assert!(StreetNamePreType::same_street((None, "SIXTH"), (None, "6")));
+
+    let city = CommonAddress {
+        number: 100,
+        directional: Some(StreetNamePreDirectional::NORTHEAST),
+        street_name: "6TH".to_string(),
+        street_type: Some(StreetNamePostType::STREET),
+        ..Default::default()
+    };
+    let county = CommonAddress {
+        street_name: "SIXTH".to_string(),
+        ..city.clone()
+    };
+    assert!(county.coincident(&city).coincident);
+    assert_eq!(city.label_with(OrdinalStyle::Spelled), "100 NE SIXTH ST");
+    assert_eq!(county.label_with(OrdinalStyle::Numeric), "100 NE 6TH ST");
+    assert_eq!(county.label(), "100 NE SIXTH ST");
+    let cities = [city];
+    let index = AddressIndex::new(&cities);
+    assert_eq!(index.candidates(100, None, "SIXTH").len(), 1);
+
+    // Highway routes keyed by route number match through the index as they do linearly.
+    let highway = SpatialAddress {
+        address: CommonAddress {
+            pre_type: Some(StreetNamePreType::Highway),
+            street_name: "199".to_string(),
+            street_type: None,
+            ..main_st().address
+        },
+        ..main_st()
+    };
+    let targets = [highway.clone()];
+    let policy = MatchPolicy::default();
+    let linear = MatchRecords::new(&highway, &targets, &policy);
+    let indexed = MatchRecords::compare(std::slice::from_ref(&highway), &targets, &policy);
+    assert_eq!(linear[0].match_status, MatchStatus::Matching);
+    assert_eq!(indexed[0].match_status, linear[0].match_status);
+    let geo = GeoAddresses::from(&targets[..]);
+    let partials = PartialAddresses::new(vec![Parse::address("100 HWY 199")?.1]);
+    let geocoded = geo.geocode(&partials);
+    let linear = MatchPartialRecord::compare(&partials[0], &targets);
+    assert_eq!(linear[0].match_status(), MatchStatus::Matching);
+    assert_eq!(geocoded[0].match_status(), linear[0].match_status());
+    Ok(())
+}
+
+#[test]
+fn validation_rules() -> anyhow::Result<()> {
+    let valid = CommonAddress {
+        number: 100,
+        street_name: "MAIN".to_string(),
+        street_type: Some(StreetNamePostType::STREET),
+        zip: 97526,
+        postal_community: "GRANTS PASS".to_string(),
+        ..Default::default()
+    };
+    let malformed = [
+        valid.clone(),
+        CommonAddress {
+            zip: 97501,
+            ..valid.clone()
+        },
+        CommonAddress {
+            street_type: None,
+            ..valid.clone()
+        },
+        CommonAddress {
+            subaddress_type: Some(SubaddressType::Apartment),
+            ..valid.clone()
+        },
+        CommonAddress {
+            subaddress_type: Some(SubaddressType::Rear),
+            ..valid.clone()
+        },
+        CommonAddress {
+            subaddress_type: Some(SubaddressType::Space),
+            subaddress_id: Some("4".to_string()),
+            floor: Some(2),
+            ..valid.clone()
+        },
+    ];
+    let findings = Validator::default().validate(&malformed);
+    let rules = findings
+        .iter()
+        .map(|finding| (finding.record, finding.rule))
+        .collect::<Vec<(usize, ValidationRule)>>();
+    assert_eq!(
+        rules,
+        vec![
+            (1, ValidationRule::UnknownZip),
+            (2, ValidationRule::MissingPostType),
+            (3, ValidationRule::MissingSubaddressIdentifier),
+            (5, ValidationRule::FloorOnSingleStory),
+        ]
+    );
+    assert_eq!(findings.by_record().len(), 4);
+
+    // Global IDs and creation dates come from the city address records.
+    let header = "Add_Number,AddNum_Suf,St_PreDir,StreetNamePreModifier,StreetNamePreType,\
+        StreetNameSeparator,St_Name,St_PosTyp,St_PosDir,SubaddressType,SubaddressIdentifier,Floor,\
+        Building,Post_Code,STATUS,NOTIFICATION,NOTES,GlobalID,created_user,created_date,\
+        last_edited_user,last_edited_date,CompleteAddressNumber,CompleteStreetName,\
+        CompleteSubaddress,CompleteStreetAddress,FULLADDRESS,PlaceStateZip,Post_Comm,StateName,\
+        Inc_Muni,Uninc_Comm";
+    let row = |number: i64, status: &str, id: &str, created: &str| {
+        format!(
+            "{number},,,,,,MAIN,STREET,,,,,,97526,{status},,,{id},,{created},,{created},\
+            {number},MAIN ST,,{number} MAIN ST,{number} MAIN ST,,GRANTS PASS,Oregon,,"
+        )
+    };
+    let text = [
+        header.to_string(),
+        row(100, "Current", "{A1}", "2020-01-01"),
+        row(102, "Temporary", "{a1}", "1/15/2020 3:00:00 PM"),
+        row(104, "Temporary", "{B2}", "2020/01/15"),
+        row(106, "Current", "{C3}", "2020-01-01"),
+    ]
+    .join("\n");
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let records = reader
+        .deserialize::<GrantsPassAddress>()
+        .collect::<Result<Vec<GrantsPassAddress>, csv::Error>>()?;
+    let findings = Validator::new(30).validate(&records);
+    let rules = findings
+        .iter()
+        .map(|finding| (finding.record, finding.rule))
+        .collect::<Vec<(usize, ValidationRule)>>();
+    assert_eq!(
+        rules,
+        vec![
+            (0, ValidationRule::DuplicateGlobalId),
+            (1, ValidationRule::StaleTemporary),
+            (1, ValidationRule::DuplicateGlobalId),
+            (2, ValidationRule::StaleTemporary),
+        ]
+    );
+    let mut validator = Validator::new(365_000);
+    validator.rules = vec![ValidationRule::match_mixed("stale_temporary").unwrap()];
+    assert!(validator.validate(&records).is_empty());
+    Ok(())
+}
+
+#[test]
+fn intersections() -> anyhow::Result<()> {
+    let connectors = [
+        "6TH ST & G ST",
+        "6TH ST AND G ST",
+        "6th St at G St",
+        "6TH ST / G ST",
+        "6TH ST@G ST, GRANTS PASS",
+    ];
+    for label in connectors {
+        let (_, intersection) = Parse::intersection(label)?;
+        let intersection = intersection.expect(label);
+        assert_eq!(intersection.label(), "6TH ST & G ST", "{label}");
+    }
+    let (_, directional) = Parse::intersection("NE 7TH / F ST")?;
+    let directional = directional.expect("Intersection reads.");
+    assert_eq!(
+        directional.first.pre_directional,
+        Some(StreetNamePreDirectional::NORTHEAST)
+    );
+    assert_eq!(directional.first.street_name, "7TH");
+    assert_eq!(directional.second.street_name, "F");
+    // Connector words inside street names do not split the streets.
+    let (_, sandy) = Parse::intersection("SANDY LN AND ATLAS DR")?;
+    let sandy = sandy.expect("Intersection reads.");
+    assert_eq!(sandy.label(), "SANDY LN & ATLAS DR");
+    // Addresses do not read as intersections.
+    assert_eq!(Parse::intersection("100 MAIN ST")?.1, None);
+
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let point = directional
+        .resolve(&city_addresses)
+        .expect("Intersection resolves.");
+    info!(
+        "{directional} near {} and {}, separation {:.1}.",
+        point.first_address, point.second_address, point.separation
+    );
+    assert!(point.first_address.contains("NE 7TH ST"));
+    assert!(point.second_address.contains("NE F ST"));
+    // Addresses flanking a corner lie within a city block of each other.
+    assert!(point.separation < 100.0);
+    let (_, missing) = Parse::intersection("NE 7TH ST & NOWHERE ST")?;
+    assert!(missing
+        .expect("Intersection reads.")
+        .resolve(&city_addresses)
+        .is_none());
+    Ok(())
+}
+
 #[test]
 fn address_parser() -> anyhow::Result<()> {
     let a1 = "1002 RAMSEY AVE, GRANTS PASS";
@@ -425,6 +1144,42 @@ fn address_parser() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn address_diagnostics() -> anyhow::Result<()> {
+    let a1 = "932 SW MOUNTAIN VIEW AVE, Grants Pass OR 97526 ATTN";
+    let (_, d1) = Parse::address_diagnostics(a1)?;
+    assert_eq!(d1.address, Parse::address(a1)?.1);
+    let name = d1.component(AddressComponent::StreetName).unwrap();
+    assert_eq!(&a1[name.start..name.end], "MOUNTAIN VIEW");
+    assert_eq!(name.ambiguity, Some(Ambiguity::PostTypeOrStreetName));
+    assert_eq!(name.confidence(), Confidence::Low);
+    let zip = d1.component(AddressComponent::Zip).unwrap();
+    assert_eq!((zip.start, zip.end), (41, 46));
+    assert_eq!(d1.unconsumed.len(), 1);
+    assert_eq!(d1.unconsumed[0].text, "ATTN");
+    assert!(!d1.is_confident());
+
+    // Unrecognized postal communities are read but not used.
+    let (_, d2) = Parse::address_diagnostics("100 MAIN ST, Smallville")?;
+    assert_eq!(d2.unconsumed[0].text, "Smallville");
+    assert!(d2.ambiguities().is_empty());
+
+    let (_, d3) = Parse::address_diagnostics("100 WEST ST")?;
+    let name = d3.component(AddressComponent::StreetName).unwrap();
+    assert_eq!(name.ambiguity, Some(Ambiguity::DirectionalOrStreetName));
+    let (_, d4) = Parse::address_diagnostics("200 WEST SIDE RD")?;
+    let directional = d4.component(AddressComponent::PreDirectional).unwrap();
+    assert_eq!(
+        directional.ambiguity,
+        Some(Ambiguity::DirectionalOrStreetName)
+    );
+
+    let (_, d5) = Parse::address_diagnostics("1012 NW 6TH ST")?;
+    assert!(d5.is_confident());
+    assert_eq!(d5.components.len(), 4);
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(feature = "ci", ignore)]
 fn load_fire_inspections() -> anyhow::Result<()> {
@@ -516,6 +1271,332 @@ fn parse_address_sample() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Checks that free-text addresses in a csv geocode against the city addresses.
+#[test]
+fn geocode_sample() -> anyhow::Result<()> {
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let reference = GeoAddresses::from(&city_addresses[..]);
+    let output = std::env::temp_dir().join(format!("geocode_sample_{}.csv", std::process::id()));
+    reference.geocode_csv("data/address_sample.csv", "address", &output)?;
+    let mut reader = csv::Reader::from_path(&output)?;
+    let headers = reader.headers()?.clone();
+    assert_eq!(
+        headers.iter().collect::<Vec<&str>>(),
+        vec![
+            "address",
+            "zip",
+            "latitude",
+            "longitude",
+            "matched_label",
+            "match_status"
+        ]
+    );
+    let rows = reader.records().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(rows.len(), 496);
+    let matching = rows.iter().filter(|r| &r[5] == "Matching").count();
+    info!("{matching} of {} sample addresses matching.", rows.len());
+    assert!(matching > rows.len() / 2);
+    for row in rows.iter().filter(|r| &r[5] == "Matching") {
+        assert!(row[2].parse::<f64>().is_ok());
+        assert!(!row[4].is_empty());
+    }
+    assert!(reference
+        .geocode_csv("data/address_sample.csv", "street", &output)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn nearest_addresses() -> anyhow::Result<()> {
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let index = SpatialIndex::new(&city_addresses[..]);
+    assert_eq!(index.len(), city_addresses.len());
+    let subject = &city_addresses[100];
+    let nearest = index.nearest(subject.x(), subject.y(), 5);
+    assert_eq!(nearest.len(), 5);
+    assert_eq!(nearest[0].distance, 0.0);
+    assert!(nearest.windows(2).all(|w| w[0].distance <= w[1].distance));
+    // Query from a point offset from the subject address.
+    let (x, y) = (subject.x() + 250.0, subject.y() - 100.0);
+    let within = index.within(x, y, 500.0);
+    let expected = city_addresses
+        .iter()
+        .filter(|a| ((a.x() - x).powi(2) + (a.y() - y).powi(2)).sqrt() <= 500.0)
+        .count();
+    assert_eq!(within.len(), expected);
+    assert!(within.iter().all(|n| n.distance <= 500.0));
+    assert_eq!(index.nearest(x, y, 1)[0].distance, within[0].distance);
+
+    let points = std::env::temp_dir().join(format!("nearest_points_{}.csv", std::process::id()));
+    let mut writer = csv::Writer::from_path(&points)?;
+    writer.write_record(["id", "x", "y"])?;
+    writer.write_record(["1", &subject.x().to_string(), &subject.y().to_string()])?;
+    writer.write_record(["2", "", ""])?;
+    writer.flush()?;
+    let output = std::env::temp_dir().join(format!("nearest_output_{}.csv", std::process::id()));
+    city_addresses.nearest_csv(&points, "x", "y", Projection::WebMercator, 3, None, &output)?;
+    let rows = csv::Reader::from_path(&output)?
+        .records()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(rows.len(), 4);
+    assert_eq!(&rows[0][3], "1");
+    assert_eq!(&rows[0][5], "0");
+    assert!(rows[3][3].is_empty());
+    assert!(city_addresses
+        .nearest_csv(&points, "lon", "lat", Projection::Wgs84, 1, None, &output)
+        .is_err());
+    // GPS points in longitude and latitude find the same address.
+    let gps = std::env::temp_dir().join(format!("nearest_gps_points_{}.csv", std::process::id()));
+    let mut writer = csv::Writer::from_path(&gps)?;
+    writer.write_record(["id", "lon", "lat"])?;
+    writer.write_record([
+        "1",
+        &subject.longitude().to_string(),
+        &subject.latitude().to_string(),
+    ])?;
+    writer.flush()?;
+    let gps_output =
+        std::env::temp_dir().join(format!("nearest_gps_output_{}.csv", std::process::id()));
+    city_addresses.nearest_csv(&gps, "lon", "lat", Projection::Wgs84, 1, None, &gps_output)?;
+    let rows = csv::Reader::from_path(&gps_output)?
+        .records()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(&rows[0][4], &subject.label());
+    Ok(())
+}
+
+#[test]
+fn projection_conversions() -> anyhow::Result<()> {
+    // City address points carry Web Mercator coordinates.
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    for address in city_addresses.iter().step_by(1000) {
+        let point = address.project(Projection::WebMercator);
+        assert!(point.distance(address) < 0.01);
+        let (longitude, latitude) = Projection::WebMercator.to_geographic(address.x, address.y);
+        assert!((longitude - address.longitude).abs() < 1e-7);
+        assert!((latitude - address.latitude).abs() < 1e-7);
+    }
+    // The origin of Oregon State Plane South lies at the false easting.
+    let origin =
+        Point::new(-120.5, 41.0 + 40.0 / 60.0, Projection::Wgs84).to(Projection::OregonSouth);
+    assert!((origin.x - 4_921_259.842_5).abs() < 0.001);
+    assert!(origin.y.abs() < 0.001);
+    // A point in Grants Pass, against its EPSG:2270 coordinates in feet from the formulas of
+    // EPSG Guidance Note 7-2, independent of the crate.
+    let grants_pass = Point::new(-123.328, 42.439, Projection::Wgs84).to(Projection::OregonSouth);
+    assert!((grants_pass.x - 4_158_022.945).abs() < 3.0);
+    assert!((grants_pass.y - 294_364.447).abs() < 3.0);
+    // Round trips between each pair of projections.
+    let subject = &city_addresses[0];
+    let start = Point::new(subject.x, subject.y, Projection::WebMercator);
+    for projection in [Projection::Wgs84, Projection::OregonSouth] {
+        let back = start.to(projection).to(Projection::WebMercator);
+        assert!(back.distance(&start) < 0.001);
+    }
+    let state_plane = start.to(Projection::OregonSouth);
+    assert!((state_plane.latitude() - subject.latitude).abs() < 1e-7);
+    assert_eq!(
+        Projection::match_mixed("EPSG:2270"),
+        Some(Projection::OregonSouth)
+    );
+    // Distances agree across projections, allowing for the Web Mercator scale factor.
+    let other = &city_addresses[1];
+    let feet = subject
+        .project(Projection::OregonSouth)
+        .distance(&other.project(Projection::OregonSouth));
+    let meters = subject.distance(other) * subject.latitude.to_radians().cos();
+    assert!((feet * 0.3048 - meters).abs() < 0.01 * meters);
+    // Building a spatial address from state plane coordinates.
+    let mut address = SpatialAddress::from_projected(
+        subject,
+        state_plane.x,
+        state_plane.y,
+        Projection::OregonSouth,
+    );
+    address.reproject(Projection::WebMercator);
+    assert!(address.distance(subject) < 0.001);
+
+    // Business points are in Web Mercator, and fall near the city address points in state plane.
+    #[derive(Clone, serde::Deserialize)]
+    struct BusinessPoint {
+        x_coordinate: f64,
+        y_coordinate: f64,
+    }
+    let points = from_csv::<BusinessPoint, &str>("data/business_points.csv")?;
+    let mut state_plane_addresses = city_addresses.clone();
+    state_plane_addresses.reproject(Projection::OregonSouth);
+    let index = SpatialIndex::new(&state_plane_addresses[..]);
+    let near = points
+        .iter()
+        .map(|p| {
+            Point::new(p.x_coordinate, p.y_coordinate, Projection::WebMercator)
+                .to(Projection::OregonSouth)
+        })
+        .filter(|p| index.nearest(p.x, p.y, 1)[0].distance < 300.0)
+        .count();
+    info!(
+        "{near} of {} business points near an address.",
+        points.len()
+    );
+    assert!(near > points.len() / 2);
+    Ok(())
+}
+
+#[test]
+fn geojson_round_trip() -> anyhow::Result<()> {
+    let city_addresses = SpatialAddresses::load("data/addresses.data")?;
+    let path = std::env::temp_dir().join(format!("city_addresses_{}.geojson", std::process::id()));
+    city_addresses.to_geojson(&path)?;
+    let collection: serde_json::Value = serde_json::from_reader(std::fs::File::open(&path)?)?;
+    assert_eq!(collection["type"], "FeatureCollection");
+    let feature = &collection["features"][0];
+    assert_eq!(feature["geometry"]["type"], "Point");
+    assert_eq!(
+        feature["geometry"]["coordinates"][1],
+        city_addresses[0].latitude
+    );
+    assert_eq!(
+        feature["properties"]["street_name"],
+        city_addresses[0].street_name().as_str()
+    );
+    let raw = SpatialAddressesRaw::from_geojson(&path)?;
+    let restored = SpatialAddresses::from(raw);
+    assert_eq!(restored.len(), city_addresses.len());
+    assert_eq!(restored, city_addresses);
+
+    // Coordinates come from the point geometry when missing from the properties.
+    let subject = &city_addresses[0];
+    let mut properties = feature["properties"].clone();
+    for key in ["latitude", "longitude", "x", "y"] {
+        properties.as_object_mut().unwrap().remove(key);
+    }
+    let sparse = serde_json::json!({
+        "type": "FeatureCollection",
+        "features": [{"type": "Feature", "geometry": feature["geometry"], "properties": properties}],
+    });
+    let sparse_path =
+        std::env::temp_dir().join(format!("sparse_address_{}.geojson", std::process::id()));
+    std::fs::write(&sparse_path, sparse.to_string())?;
+    let raw = SpatialAddressesRaw::from_geojson(&sparse_path)?;
+    assert_eq!(raw.len(), 1);
+    assert_eq!(raw[0].longitude, subject.longitude);
+    assert!((raw[0].x - subject.x).abs() < 0.01);
+
+    let source = vec![subject.clone()];
+    let records = MatchRecords::compare(&source, &city_addresses, &MatchPolicy::default());
+    let match_path =
+        std::env::temp_dir().join(format!("match_records_{}.geojson", std::process::id()));
+    records.to_geojson(&match_path)?;
+    let collection: serde_json::Value = serde_json::from_reader(std::fs::File::open(&match_path)?)?;
+    assert_eq!(
+        collection["features"][0]["properties"]["match_status"],
+        "Matching"
+    );
+    Ok(())
+}
+
+#[test]
+fn standardization_rules() -> anyhow::Result<()> {
+    let defaults = Rules::default_rules();
+    assert!(defaults.len() > 20);
+
+    let mut azalea = CommonAddress {
+        number: 100,
+        street_name: "AZALEA DRIVE".to_string(),
+        ..Default::default()
+    };
+    azalea.standardize();
+    assert_eq!(azalea.street_name, "AZALEA");
+    assert_eq!(azalea.street_type, Some(StreetNamePostType::DriveCutoff));
+
+    let mut west_side = CommonAddress {
+        number: 100,
+        directional: Some(StreetNamePreDirectional::WEST),
+        street_name: "SIDE ROAD".to_string(),
+        subaddress_id: Some("APT 5".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(defaults.apply(&mut west_side), 2);
+    assert_eq!(west_side.directional, None);
+    assert_eq!(west_side.street_name, "WEST SIDE");
+    assert_eq!(west_side.subaddress_id, Some("5".to_string()));
+
+    let mut office = CommonAddress {
+        street_name: "LEWIS".to_string(),
+        subaddress_id: Some("OFFICE".to_string()),
+        ..Default::default()
+    };
+    office.standardize();
+    assert_eq!(office.subaddress_id, None);
+    assert_eq!(office.subaddress_type, Some(SubaddressType::Office));
+
+    let (_, mut partial) = Parse::address("2350 W SIDE RD")?;
+    partial.standardize();
+    assert_eq!(partial.street_name_pre_directional(), None);
+    assert_eq!(partial.street_name(), Some("WEST SIDE".to_string()));
+
+    // Citify rules only apply in the quadrant used by the city.
+    let citify = Rules::citify_rules();
+    let mut quail = CommonAddress {
+        directional: Some(StreetNamePreDirectional::NORTHEAST),
+        street_name: "QUAIL CROSSING".to_string(),
+        ..Default::default()
+    };
+    let mut southwest = CommonAddress {
+        directional: Some(StreetNamePreDirectional::SOUTHWEST),
+        ..quail.clone()
+    };
+    assert_eq!(citify.apply(&mut quail), 1);
+    assert_eq!(citify.apply(&mut southwest), 0);
+    assert_eq!(quail.street_name, "QUAIL");
+    assert_eq!(southwest.street_name, "QUAIL CROSSING");
+
+    // Custom rules replace the defaults.
+    let toml_path = std::env::temp_dir().join(format!("custom_rules_{}.toml", std::process::id()));
+    std::fs::write(
+        &toml_path,
+        "[[rule]]\nname = \"Main Street\"\nstreet_name = \"MAIN\"\ndirectional = \"N\"\nset_street_type = \"AVE\"\n",
+    )?;
+    let custom = Rules::from_path(&toml_path)?;
+    let mut main = CommonAddress {
+        directional: Some(StreetNamePreDirectional::NORTH),
+        street_name: "MAIN".to_string(),
+        street_type: Some(StreetNamePostType::STREET),
+        ..Default::default()
+    };
+    let mut azalea = CommonAddress {
+        street_name: "AZALEA DRIVE".to_string(),
+        ..Default::default()
+    };
+    main.standardize_with(&custom);
+    azalea.standardize_with(&custom);
+    assert_eq!(main.street_type, Some(StreetNamePostType::AVENUE));
+    assert_eq!(azalea.street_name, "AZALEA DRIVE");
+
+    let csv_path = std::env::temp_dir().join(format!("custom_rules_{}.csv", std::process::id()));
+    std::fs::write(
+        &csv_path,
+        "name,street_name,set_street_name,set_street_type\nOak Glen,OAK GLEN,OAK,GLEN\n",
+    )?;
+    let custom = Rules::from_path(&csv_path)?;
+    assert_eq!(custom.len(), 1);
+    let mut oak = CommonAddress {
+        street_name: "OAK GLEN".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(custom.apply(&mut oak), 1);
+    assert_eq!(oak.street_type, Some(StreetNamePostType::GLEN));
+
+    let invalid_path =
+        std::env::temp_dir().join(format!("invalid_rules_{}.csv", std::process::id()));
+    std::fs::write(
+        &invalid_path,
+        "name,street_name,set_street_type\nBad,OAK,NOT A TYPE\n",
+    )?;
+    assert!(Rules::from_path(&invalid_path).is_err());
+    Ok(())
+}
+
 // Checks that city address labels parse back to their parent address
 #[test]
 #[cfg_attr(feature = "ci", ignore)]
@@ -625,3 +1706,503 @@ fn business_mailing() -> anyhow::Result<()> {
     tracing::info!("Mailing list: {} records", mail.len());
     Ok(())
 }
+
+/// The `main_st` function returns the address "100 MAIN ST, GRANTS PASS 97526", from which the
+/// address comparison and export tests derive their variants.
+fn main_st() -> SpatialAddress {
+    SpatialAddress {
+        address: CommonAddress {
+            number: 100,
+            street_name: "MAIN".to_string(),
+            street_type: Some(StreetNamePostType::STREET),
+            zip: 97526,
+            postal_community: "GRANTS PASS".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn nena_export() -> anyhow::Result<()> {
+    let address = SpatialAddress {
+        address: CommonAddress {
+            number: 701,
+            directional: Some(StreetNamePreDirectional::NORTHEAST),
+            street_name: "F".to_string(),
+            subaddress_type: Some(SubaddressType::Apartment),
+            subaddress_id: Some("4".to_string()),
+            ..main_st().address
+        },
+        latitude: 42.44,
+        longitude: -123.32,
+        ..main_st()
+    };
+    let mut points = NenaSiteAddressPoints::from(&[address][..]);
+    let point = &points[0];
+    assert_eq!(point.address_number, 701);
+    assert_eq!(
+        point.street_name_pre_directional.as_deref(),
+        Some("NORTHEAST")
+    );
+    assert_eq!(point.street_name, "F");
+    assert_eq!(point.street_name_post_type.as_deref(), Some("STREET"));
+    assert_eq!(point.unit.as_deref(), Some("APARTMENT 4"));
+    assert_eq!(point.county.as_deref(), Some("JOSEPHINE"));
+    assert_eq!(point.state, "OR");
+    assert_eq!(point.country, "US");
+    assert_eq!(point.postal_code, "97526");
+    assert_eq!(point.longitude, -123.32);
+
+    let path = std::env::temp_dir().join(format!("nena_export_{}.csv", std::process::id()));
+    points.to_csv(&path)?;
+    let header = std::fs::read_to_string(&path)?;
+    assert!(header.starts_with("DiscrpAgID,DateUpdate,Effective,Expire,Site_NGUID,Country"));
+    let read = NenaSiteAddressPoints::from_csv(&path)?;
+    assert_eq!(read, points);
+    Ok(())
+}
+
+#[test]
+fn nguid_generation() -> anyhow::Result<()> {
+    let generator = NguidGenerator::new("GrantsPassOregon.gov");
+    assert_eq!(
+        generator.nguid("1234"),
+        "urn:emergency:uid:gis:SSAP:1234:grantspassoregon.gov"
+    );
+    assert_eq!(
+        NguidGenerator::parse(&generator.nguid("1234")),
+        Some(("SSAP", "1234", "grantspassoregon.gov"))
+    );
+    assert_eq!(NguidGenerator::parse("urn:emergency:uid:gis:SSAP"), None);
+
+    // Addresses without a Global ID derive the local id from the address.
+    let address = main_st();
+    let other = SpatialAddress {
+        address: CommonAddress {
+            number: 102,
+            ..address.address.clone()
+        },
+        ..address.clone()
+    };
+    assert_eq!(
+        NguidGenerator::stable_id(&address),
+        NguidGenerator::stable_id(&address.clone())
+    );
+    assert_ne!(
+        NguidGenerator::stable_id(&address),
+        NguidGenerator::stable_id(&other)
+    );
+    let points = NenaSiteAddressPoints::with_nguid(std::slice::from_ref(&address), &generator);
+    assert_eq!(points[0].nguid, Some(generator.address_nguid(&address)),);
+    assert_eq!(
+        points[0].discrepancy_agency_id.as_deref(),
+        Some("grantspassoregon.gov")
+    );
+
+    // Match records keep the same id between runs, falling back to the stable id without a
+    // Global ID.
+    let first = MatchRecords::new(
+        &address,
+        std::slice::from_ref(&other),
+        &MatchPolicy::default(),
+    );
+    let second = MatchRecords::new(&address, &[other], &MatchPolicy::default());
+    assert_eq!(first[0].id, second[0].id);
+    assert_eq!(first[0].id, NguidGenerator::record_id(&address));
+    assert_eq!(first[0].id, NguidGenerator::stable_id(&address));
+    assert_eq!(first[0].id.get_version_num(), 5);
+    Ok(())
+}
+
+#[test]
+fn mailing_labels() -> anyhow::Result<()> {
+    let address = CommonAddress {
+        number: 701,
+        directional: Some(StreetNamePreDirectional::NORTHEAST),
+        street_name: "F".to_string(),
+        subaddress_type: Some(SubaddressType::Apartment),
+        subaddress_id: Some("4".to_string()),
+        postal_community: "Grants Pass".to_string(),
+        ..main_st().address
+    };
+    let label = MailingLabel::new(&address)?;
+    assert_eq!(label.to_string(), "701 NE F ST APT 4\nGRANTS PASS OR 97526");
+    let label = MailingLabel::with_zip4(&address, Some(1234))?;
+    assert_eq!(label.last_line, "GRANTS PASS OR 97526-1234");
+    assert_eq!(MailingLabel::zip_code(975260042, None), "97526-0042");
+
+    // The secondary unit moves to the line above when the delivery line is too long.
+    let long = CommonAddress {
+        number: 12345,
+        street_name: "REDWOOD MOUNTAIN VIEWPOINT".to_string(),
+        ..address.clone()
+    };
+    let label = MailingLabel::new(&long)?;
+    assert_eq!(
+        label.lines(),
+        vec![
+            "APT 4",
+            "12345 NE REDWOOD MOUNTAIN VIEWPOINT ST",
+            "GRANTS PASS OR 97526"
+        ]
+    );
+    assert!(label
+        .lines()
+        .iter()
+        .all(|line| line.len() <= MailingLabel::LINE_WIDTH));
+    let too_long = CommonAddress {
+        street_name: "THE EXCEEDINGLY LONG AND WINDING MOUNTAIN ROAD".to_string(),
+        ..long
+    };
+    assert!(MailingLabel::new(&too_long)
+        .is_err_and(|e| e.to_string().contains("exceeds 40 characters")));
+    assert_eq!(MailingLabels::from_addresses(&[address, too_long]).len(), 1);
+
+    // Pre types print abbreviated.
+    let highway = CommonAddress {
+        number: 100,
+        pre_type: Some(StreetNamePreType::UsHighway),
+        street_name: "199".to_string(),
+        zip: 97527,
+        postal_community: "GRANTS PASS".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(MailingLabel::new(&highway)?.delivery_line, "100 US HWY 199");
+    Ok(())
+}
+
+#[test]
+fn match_policy() -> anyhow::Result<()> {
+    let city = main_st();
+    let county = SpatialAddress {
+        address: CommonAddress {
+            zip: 97527,
+            postal_community: "MERLIN".to_string(),
+            floor: Some(2),
+            ..city.address.clone()
+        },
+        ..city.clone()
+    };
+
+    // The default policy requires the zip code and postal community to agree.
+    assert!(!city.coincident(&county).coincident);
+    let records = MatchRecords::new(
+        &city,
+        std::slice::from_ref(&county),
+        &MatchPolicy::default(),
+    );
+    assert_eq!(records[0].match_status, MatchStatus::Missing);
+
+    // The county policy ignores them, and still reports the floor.
+    let policy = MatchPolicy::match_mixed("County").unwrap();
+    let records = MatchRecords::new(&city, std::slice::from_ref(&county), &policy);
+    assert_eq!(records[0].match_status, MatchStatus::Divergent);
+    assert!(records[0].floor.is_some());
+    let policy = MatchPolicy {
+        floor: ComponentPolicy::Ignored,
+        ..policy
+    };
+    let records = MatchRecords::compare(
+        std::slice::from_ref(&city),
+        std::slice::from_ref(&county),
+        &policy,
+    );
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+
+    // A street name that need not agree finds candidates by address number.
+    let renamed = SpatialAddress {
+        address: CommonAddress {
+            street_name: "MAPLE".to_string(),
+            ..city.address.clone()
+        },
+        ..city.clone()
+    };
+    let policy = MatchPolicy {
+        street_name: ComponentPolicy::Ignored,
+        ..Default::default()
+    };
+    let index = AddressIndex::new(std::slice::from_ref(&renamed));
+    let records = MatchRecords::indexed(&city, &index, &policy);
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+
+    // Policies read from TOML, and omitted components take their default.
+    let path = std::env::temp_dir().join(format!("match_policy_{}.toml", std::process::id()));
+    std::fs::write(&path, "zip = \"Ignored\"\nstatus = \"Required\"\n")?;
+    let policy = MatchPolicy::from_name_or_path(&path.to_string_lossy())?;
+    assert_eq!(policy.zip, ComponentPolicy::Ignored);
+    assert_eq!(policy.status, ComponentPolicy::Required);
+    assert_eq!(policy.postal_community, ComponentPolicy::Required);
+    Ok(())
+}
+
+#[test]
+fn component_mismatches() -> anyhow::Result<()> {
+    let city = main_st();
+    let county = SpatialAddress {
+        address: CommonAddress {
+            street_type: Some(StreetNamePostType::AVENUE),
+            zip: 97527,
+            ..city.address.clone()
+        },
+        ..city.clone()
+    };
+    let policy = MatchPolicy {
+        street_type: ComponentPolicy::Reported,
+        zip: ComponentPolicy::Reported,
+        ..Default::default()
+    };
+    let records = MatchRecords::compare(
+        &[city.clone(), county.clone()],
+        std::slice::from_ref(&county),
+        &policy,
+    );
+    assert_eq!(records[0].match_status, MatchStatus::Divergent);
+    assert_eq!(records[0].zip.as_deref(), Some("97526 not equal to 97527"));
+    assert!(records[0].street_type.is_some());
+    assert!(records[0].postal_community.is_none());
+    assert_eq!(records[1].match_status, MatchStatus::Matching);
+
+    assert_eq!(records.clone().filter("zip").len(), 1);
+    // Probable records with a mismatch in the component count too.
+    let mut probable = records.clone();
+    probable[0].match_status = MatchStatus::Probable;
+    assert_eq!(probable.filter("zip").len(), 1);
+    assert_eq!(records.clone().filter("post-type").len(), 1);
+    assert_eq!(records.clone().filter("postal_community").len(), 0);
+    assert_eq!(records.clone().filter("matching").len(), 1);
+    assert!(records[0].mismatch("not a component").is_none());
+    assert_eq!(
+        MatchRecord::component("subaddress"),
+        Some("subaddress_type")
+    );
+
+    // Match records written before the component columns still read.
+    let path = std::env::temp_dir().join(format!("match_records_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "match_status,address_label,subaddress_type,floor,building,status,longitude,latitude,id\n\
+        Divergent,100 MAIN ST,,,,status mismatch,0.0,0.0,67e55044-10b1-426f-9247-bb680e5fe0c8\n",
+    )?;
+    let old = MatchRecords::from_csv(&path)?;
+    assert_eq!(old[0].status.as_deref(), Some("status mismatch"));
+    assert_eq!(old[0].zip, None);
+    Ok(())
+}
+
+#[test]
+fn reconciliation() -> anyhow::Result<()> {
+    let main = main_st();
+    let floor = SpatialAddress {
+        address: CommonAddress {
+            floor: Some(2),
+            ..main.address.clone()
+        },
+        ..main.clone()
+    };
+    let oak = SpatialAddress {
+        address: CommonAddress {
+            street_name: "OAK".to_string(),
+            ..main.address.clone()
+        },
+        ..main.clone()
+    };
+    let elm = SpatialAddress {
+        address: CommonAddress {
+            street_name: "ELM".to_string(),
+            ..main.address.clone()
+        },
+        ..main.clone()
+    };
+
+    let source = vec![main.clone(), oak];
+    let target = vec![floor, elm];
+    let records = ReconcileRecords::reconcile(&source, &target, &MatchPolicy::default());
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].status, ReconcileStatus::Divergent);
+    assert_eq!(
+        records[0].mismatches.as_deref(),
+        Some("floor: None not equal to Some(2)")
+    );
+    assert_eq!(records[1].status, ReconcileStatus::SourceOnly);
+    assert_eq!(records[1].source_label.as_deref(), Some("100 OAK ST"));
+    assert_eq!(records[2].status, ReconcileStatus::TargetOnly);
+    assert_eq!(records[2].target_label.as_deref(), Some("100 ELM ST"));
+
+    let summary = records.summary();
+    assert_eq!(summary.count(ReconcileStatus::Divergent), 1);
+    assert_eq!(summary.source_only, 1);
+    assert_eq!(summary.target_only, 1);
+    assert_eq!(summary.matching, 0);
+    assert_eq!(records.filter("target only").len(), 1);
+
+    // Two source addresses matching one target address pair only once.
+    let source = vec![main.clone(), main.clone()];
+    let target = vec![main.clone()];
+    let summary = ReconcileRecords::reconcile(&source, &target, &MatchPolicy::default()).summary();
+    assert_eq!(summary.matching, 1);
+    assert_eq!(summary.source_only, 1);
+    assert_eq!(summary.target_only, 0);
+
+    // Of two targets sharing a label, the one left out of the pairing is target-only.
+    let near = SpatialAddress {
+        longitude: 0.0001,
+        ..main.clone()
+    };
+    let far = SpatialAddress {
+        longitude: 1.0,
+        ..main.clone()
+    };
+    let target = vec![far, near];
+    let assignment = MatchRecords::assign(
+        std::slice::from_ref(&main),
+        &target,
+        &MatchPolicy::default(),
+    );
+    assert_eq!(assignment.unpaired, vec![0]);
+    let records = ReconcileRecords::reconcile(
+        std::slice::from_ref(&main),
+        &target,
+        &MatchPolicy::default(),
+    );
+    assert_eq!(records[1].status, ReconcileStatus::TargetOnly);
+    assert_eq!(records[1].longitude, 1.0);
+    Ok(())
+}
+
+#[test]
+fn one_to_one_assignment() -> anyhow::Result<()> {
+    let main = main_st();
+    let near = SpatialAddress {
+        longitude: 0.0001,
+        ..main.clone()
+    };
+    let far = SpatialAddress {
+        longitude: 1.0,
+        ..main.clone()
+    };
+    let floor = SpatialAddress {
+        address: CommonAddress {
+            floor: Some(2),
+            ..main.address.clone()
+        },
+        ..main.clone()
+    };
+
+    // Both exact matches pair before the divergent match, even though the divergent match is
+    // nearest.
+    let source = vec![main.clone(), main.clone(), main.clone()];
+    let target = vec![far.clone(), floor, near.clone()];
+    let MatchAssignment {
+        records,
+        unassigned,
+        ..
+    } = MatchRecords::assign(&source, &target, &MatchPolicy::default());
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+    assert_eq!(records[1].match_status, MatchStatus::Matching);
+    assert_eq!(records[2].match_status, MatchStatus::Divergent);
+    assert!(unassigned.is_empty());
+
+    // Leftover subjects and comparison addresses are unassigned on both sides.
+    let assignment = MatchRecords::assign(
+        std::slice::from_ref(&main),
+        &[far, near],
+        &MatchPolicy::default(),
+    );
+    assert_eq!(assignment.records[0].match_status, MatchStatus::Matching);
+    assert_eq!(assignment.unassigned.len(), 1);
+    assert_eq!(
+        assignment.unassigned[0].match_status,
+        MatchStatus::Unassigned
+    );
+    assert_eq!(assignment.unassigned[0].longitude, 1.0);
+    let assignment = MatchRecords::assign(
+        &source,
+        std::slice::from_ref(&main),
+        &MatchPolicy::default(),
+    );
+    assert_eq!(assignment.records.clone().filter("unassigned").len(), 2);
+    assert_eq!(assignment.records.filter("matching").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn spatial_matching() -> anyhow::Result<()> {
+    let city = SpatialAddress {
+        latitude: 42.44,
+        longitude: -123.33,
+        ..main_st()
+    };
+    let county = SpatialAddress {
+        x: 2000.0,
+        latitude: 42.46,
+        ..city.clone()
+    };
+    let geo = GeoAddresses::from(std::slice::from_ref(&county));
+
+    // Distance is in meters between the geographic coordinates, ignoring projected coordinates.
+    assert!((city.haversine(&county) - 2224.0).abs() < 1.0);
+    assert_eq!(city.haversine(&county), city.haversine(&geo[0]));
+
+    // Distance is only measured when the policy does not ignore drift.
+    let records = MatchRecords::new(
+        &city,
+        std::slice::from_ref(&county),
+        &MatchPolicy::default(),
+    );
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+    assert!(records[0].distance.is_none());
+
+    let policy = MatchPolicy::default().with_drift(1000.0);
+    let records = MatchRecords::compare(std::slice::from_ref(&city), &geo, &policy);
+    assert_eq!(records[0].match_status, MatchStatus::Divergent);
+    assert!(records[0].distance.is_some_and(|d| d > 2000.0));
+    assert!(records[0].drift.is_some());
+    assert_eq!(records.clone().filter("drift").len(), 1);
+
+    let policy = MatchPolicy {
+        drift: ComponentPolicy::Required,
+        ..policy
+    };
+    let records = MatchRecords::new(&city, std::slice::from_ref(&county), &policy);
+    assert_eq!(records[0].match_status, MatchStatus::Missing);
+    let policy = MatchPolicy {
+        max_drift: Some(5000.0),
+        ..policy
+    };
+    let records = MatchRecords::new(&city, std::slice::from_ref(&county), &policy);
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+    assert!(records[0]
+        .distance
+        .is_some_and(|d| (d - 2224.0).abs() < 1.0));
+
+    // Records with and without projected coordinates measure the same drift.
+    let projected = MatchRecords::compare(
+        std::slice::from_ref(&city),
+        std::slice::from_ref(&county),
+        &policy,
+    );
+    let geographic = MatchRecords::compare(std::slice::from_ref(&city), &geo, &policy);
+    assert_eq!(projected[0].distance, geographic[0].distance);
+
+    // Assignment ranks by geographic distance, even against the projected coordinates.
+    let near = SpatialAddress {
+        x: 5000.0,
+        latitude: 42.4401,
+        ..city.clone()
+    };
+    let far = SpatialAddress {
+        x: 0.0,
+        latitude: 42.5,
+        ..city.clone()
+    };
+    let assignment = MatchRecords::assign(
+        std::slice::from_ref(&city),
+        &[far, near],
+        &MatchPolicy::default().with_drift(10_000.0),
+    );
+    assert_eq!(assignment.unassigned.len(), 1);
+    assert_eq!(assignment.unpaired, vec![0]);
+    Ok(())
+}